@@ -18,39 +18,58 @@
 use super::plan;
 use super::plan::Plan;
 use crate::commands::helps::build;
+use crate::core::artifacts;
 use crate::core::blueprint::Scheme;
 use crate::core::catalog::Catalog;
 use crate::core::context::Context;
+use crate::core::explain::EXPLAIN_FILE;
 use crate::core::fileset::Fileset;
+use crate::core::fingerprint::Fingerprint;
+use crate::core::generic::Generic;
 use crate::core::ip::Ip;
+use crate::core::lang::cache::SymbolCache;
 use crate::core::lang::vhdl::token::Identifier;
 use crate::core::swap::StrSwapTable;
-use crate::core::target::Process;
 use crate::core::target::Target;
+use crate::core::target::SCRIPT_FILE;
+use crate::core::watch;
 use crate::error::Error;
 use crate::error::LastError;
+use crate::util::anyerror::Fault;
 use crate::util::environment::EnvVar;
 use crate::util::environment::Environment;
+use crate::util::environment::DOT_ENV_FILE;
 use crate::util::environment::ORBIT_OUT_DIR;
 use crate::util::environment::ORBIT_TARGET_DIR;
+use crate::util::logfile::LogFile;
 
 use cliproc::{cli, proc, stage::*};
 use cliproc::{Arg, Cli, Help, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Debug, PartialEq)]
 pub struct Build {
     target: Option<String>,
     list: bool,
     force: bool,
+    incremental: bool,
     dirty: bool,
     all: bool,
+    explain: bool,
+    watch: bool,
+    emit_script: bool,
     command: Option<String>,
-    top: Option<Identifier>,
+    from_step: Option<String>,
+    to_step: Option<String>,
+    top: Option<Vec<Identifier>>,
     plan: Option<Scheme>,
     target_dir: Option<String>,
     args: Vec<String>,
     verbose: bool,
+    log: bool,
+    quiet: bool,
     filesets: Option<Vec<Fileset>>,
+    generics: Option<Vec<Generic>>,
 }
 
 impl Subcommand<Context> for Build {
@@ -60,16 +79,25 @@ impl Subcommand<Context> for Build {
             // Flags
             list: cli.check(Arg::flag("list"))?,
             verbose: cli.check(Arg::flag("verbose"))?,
+            log: cli.check(Arg::flag("log"))?,
+            quiet: cli.check(Arg::flag("quiet"))?,
             force: cli.check(Arg::flag("force"))?,
+            incremental: cli.check(Arg::flag("incremental"))?,
             all: cli.check(Arg::flag("all"))?,
+            explain: cli.check(Arg::flag("explain"))?,
+            watch: cli.check(Arg::flag("watch"))?,
+            emit_script: cli.check(Arg::flag("emit-script"))?,
             dirty: cli.check(Arg::flag("no-clean"))?,
             // Options
-            top: cli.get(Arg::option("top").value("unit"))?,
+            top: cli.get_all(Arg::option("top").value("unit"))?,
             plan: cli.get(Arg::option("plan").value("format"))?,
             target: cli.get(Arg::option("target").value("name").switch('t'))?,
             target_dir: cli.get(Arg::option("target-dir").value("dir"))?,
             command: cli.get(Arg::option("command").value("path"))?,
+            from_step: cli.get(Arg::option("from-step").value("name"))?,
+            to_step: cli.get(Arg::option("to-step").value("name"))?,
             filesets: cli.get_all(Arg::option("fileset").value("key=glob"))?,
+            generics: cli.get_all(Arg::option("generic").value("name=value"))?,
             // Remaining args
             args: cli.remainder()?,
         })
@@ -93,7 +121,7 @@ impl Subcommand<Context> for Build {
                             .values()
                             .into_iter()
                             .collect::<Vec<&&Target>>(),
-                        def_target,
+                        def_target.as_ref(),
                     )
                 ),
             }
@@ -105,10 +133,37 @@ impl Subcommand<Context> for Build {
         // coordinate the plan
         let plan = target.coordinate_plan(&self.plan)?;
 
+        // verify the range of steps before planning
+        target.select_steps(&self.from_step, &self.to_step)?;
+
         // verify running from an ip directory and enter ip's root directory
         c.jump_to_working_ip()?;
 
-        let working_ip = Ip::load(c.get_ip_path().unwrap().to_path_buf(), true, false)?;
+        match self.watch {
+            true => {
+                // ignore the outputs that each run writes into the build directory
+                let default_target_dir = c.get_target_dir();
+                let target_dir = self.target_dir.as_ref().unwrap_or(&default_target_dir);
+                let root = c.get_ip_path().unwrap();
+                watch::watch(root, &root.join(target_dir), || {
+                    self.build(c, root, &target, &plan)
+                })
+            }
+            false => self.build(c, c.get_ip_path().unwrap(), &target, &plan),
+        }
+    }
+}
+
+impl Build {
+    /// Plans and runs the `target` for the working ip found at `root`.
+    fn build(
+        &self,
+        c: &Context,
+        root: &PathBuf,
+        target: &Target,
+        plan: &Scheme,
+    ) -> Result<(), Fault> {
+        let mut working_ip = Ip::load(root.to_path_buf(), true, false)?;
 
         // determine the build directory based on cli priority
         let default_target_dir = c.get_target_dir();
@@ -117,6 +172,10 @@ impl Subcommand<Context> for Build {
 
         let output_path = working_ip.get_root().join(target_dir).join(out_dir);
 
+        // reuse the symbols parsed for the working ip during previous plans
+        let symbols = SymbolCache::open(&working_ip.get_root().join(target_dir));
+        working_ip.set_symbol_cache(symbols);
+
         // gather the catalog and resolve any missing dependencies
         let catalog = Catalog::new()
             .installations(c.get_cache_path())?
@@ -131,20 +190,28 @@ impl Subcommand<Context> for Build {
             .add(EnvVar::with(ORBIT_TARGET_DIR, target_dir))
             .add(EnvVar::with(ORBIT_OUT_DIR, out_dir));
 
+        // an incremental build is only cleaned once it is known to be stale
+        let is_partial = self.from_step.is_some() || self.to_step.is_some();
+        let is_incremental = self.incremental == true && is_partial == false;
+        let clean = self.dirty == false && self.from_step.is_none();
+
         // plan for the provided target
-        Plan::run(
+        let planned = Plan::run(
             &working_ip,
             target_dir,
+            out_dir,
             target,
-            catalog,
-            self.dirty == false,
+            &catalog,
+            clean == true && is_incremental == false,
             self.force,
             false,
             self.all,
-            &None,
-            &self.top,
+            self.explain,
+            &[],
+            self.top.as_deref().unwrap_or(&[]),
             &self.filesets,
-            &plan,
+            self.generics.as_deref().unwrap_or(&[]),
+            plan,
             false,
             false,
             envs,
@@ -156,17 +223,158 @@ impl Subcommand<Context> for Build {
         let swap_table = StrSwapTable::new().load_environment(&envs)?;
         let target = target.clone().replace_vars_in_args(&swap_table);
 
+        // write a script to reproduce the run without orbit
+        if self.emit_script == true {
+            let script = target.write_script(
+                &self.command,
+                &self.args,
+                &output_path,
+                envs.into_map(),
+                &self.from_step,
+                &self.to_step,
+            )?;
+            crate::info!("script saved at: {:?}", script);
+        }
+
+        // skip running the target when its inputs match the last successful run
+        let fingerprint = match (is_incremental, planned) {
+            (true, Some((blueprint_name, files))) => {
+                let blueprint = std::fs::read_to_string(output_path.join(&blueprint_name))?;
+                let fp = Fingerprint::compute(
+                    &blueprint,
+                    &files,
+                    &target.to_string(),
+                    &self.command,
+                    &self.args,
+                    &envs,
+                );
+                if self.force == false && fp.is_fresh(&output_path) == true {
+                    crate::info!(
+                        "target {} is fresh; skipping execution",
+                        target.get_name().green()
+                    );
+                    return Ok(());
+                }
+                // keep only what was just planned and emitted from the stale outputs
+                if clean == true {
+                    let mut keep = vec![blueprint_name.as_str(), DOT_ENV_FILE, EXPLAIN_FILE];
+                    if self.emit_script == true {
+                        keep.push(SCRIPT_FILE);
+                    }
+                    artifacts::clean_output(&output_path, &keep)?;
+                }
+                Some(fp)
+            }
+            _ => None,
+        };
+        // the outputs will no longer reflect the last recorded run
+        Fingerprint::remove(&output_path)?;
+
         // run the command from the output path
         crate::info!("executing target {}", target.get_name().green());
-        match target.execute(
+        let log = match self.log || self.quiet {
+            true => Some(LogFile::create(
+                &output_path,
+                target.get_name(),
+                self.quiet,
+            )?),
+            false => None,
+        };
+        let result = target.run(
             &self.command,
             &self.args,
             self.verbose,
             &output_path,
             envs.into_map(),
-        ) {
-            Ok(()) => Ok(()),
+            &self.from_step,
+            &self.to_step,
+            log.as_ref(),
+        );
+        if let Some(log) = &log {
+            log.report(result.is_err())?;
+        }
+        match result {
+            Ok(()) => (),
             Err(e) => Err(Error::TargetProcFailed(LastError(e.to_string())))?,
         }
+        // keep the artifacts from running the entire target
+        if is_partial == false {
+            artifacts::collect_target_artifacts(&target, &output_path, &working_ip)?;
+        }
+        match fingerprint {
+            Some(fp) => fp.write(&output_path),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    /// Creates the command to run an incremental build that also emits a script
+    /// and an explanation.
+    fn incremental_build() -> Build {
+        Build {
+            target: None,
+            list: false,
+            force: false,
+            incremental: true,
+            dirty: false,
+            all: false,
+            explain: true,
+            watch: false,
+            emit_script: true,
+            command: None,
+            from_step: None,
+            to_step: None,
+            top: None,
+            plan: None,
+            target_dir: Some(String::from("target")),
+            args: Vec::new(),
+            verbose: false,
+            log: false,
+            quiet: false,
+            filesets: None,
+            generics: None,
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn keep_emitted_files_on_stale_build() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        std::fs::copy("./tests/s1/Orbit.toml", root.join("Orbit.toml")).unwrap();
+        let source = root.join("top.vhd");
+        let code = "entity top is port (clk : in bit); end entity;\narchitecture rtl of top is begin end architecture;";
+        std::fs::write(&source, code).unwrap();
+        let mut target = Target::from_str(
+            r#"
+name = "check"
+command = "sh"
+args = ["-c", "true"]
+"#,
+        )
+        .unwrap();
+        target.set_root(root.clone());
+        // the context searches its default cache and downloads directories
+        let c = Context::new();
+        std::fs::create_dir_all(c.get_cache_path()).unwrap();
+        std::fs::create_dir_all(c.get_downloads_path()).unwrap();
+        let build = incremental_build();
+        build.build(&c, &root, &target, &Scheme::Tsv).unwrap();
+
+        // change an input so the next build is stale
+        let output_path = root.join("target").join("check");
+        std::fs::write(output_path.join("old.txt"), "stale").unwrap();
+        std::fs::write(&source, format!("{}\n-- changed", code)).unwrap();
+        build.build(&c, &root, &target, &Scheme::Tsv).unwrap();
+
+        assert_eq!(output_path.join("old.txt").exists(), false);
+        assert_eq!(output_path.join(SCRIPT_FILE).exists(), true);
+        assert_eq!(output_path.join(EXPLAIN_FILE).exists(), true);
+        assert_eq!(output_path.join("blueprint.tsv").exists(), true);
     }
 }
@@ -29,6 +29,7 @@ use crate::core::protocol::Protocol;
 use crate::core::source::Source;
 use crate::core::swap::StrSwapTable;
 use crate::core::target::Process;
+use crate::core::version::AnyVersion;
 use crate::error::Error;
 use crate::error::Hint;
 use crate::error::LastError;
@@ -140,7 +141,8 @@ impl Download {
                     );
                     // allow the user to handle placing the code in the queue
                     let entry: Protocol = entry.clone().replace_vars_in_args(&vtable);
-                    if let Err(err) = entry.execute(&None, &[], verbose, &std_queue, HashMap::new())
+                    if let Err(err) =
+                        entry.execute(&None, &[], verbose, &std_queue, HashMap::new(), false, None)
                     {
                         fs::remove_dir_all(queue)?;
                         return Err(Error::ProtocolProcFailed(LastError(err.to_string())))?;
@@ -228,12 +230,12 @@ impl Download {
                             is_match = false;
                         }
                         // make sure the version falls under right domain
-                        if manifest_version.in_domain(
-                            prov.get_version()
-                                .as_specific()
-                                .unwrap_or(&manifest_version),
-                        ) == false
-                        {
+                        let in_domain = match prov.get_version() {
+                            AnyVersion::Req(r) => r.matches(temp.get_man().get_ip().get_version()),
+                            v => manifest_version
+                                .in_domain(v.as_specific().unwrap_or(&manifest_version)),
+                        };
+                        if in_domain == false {
                             is_match = false;
                         }
 
@@ -21,6 +21,7 @@ use crate::core::context::Context;
 use crate::core::ip::Ip;
 use crate::core::ip::IpSpec;
 use crate::core::ip::PartialIpSpec;
+use crate::core::lang::ir::HdlIr;
 use crate::core::lang::sv::format::SystemVerilogFormat;
 use crate::core::lang::verilog::symbols::module::Module;
 use crate::core::lang::vhdl::format::VhdlFormat;
@@ -331,7 +332,10 @@ impl Get {
 
         // print as json data
         if self.json == true {
-            println!("{}", serde_json::to_string(&entity)?);
+            println!(
+                "{}",
+                serde_json::to_string(&HdlIr::from_vhdl_entity(&entity))?
+            );
         }
         Ok(())
     }
@@ -375,7 +379,10 @@ impl Get {
 
         // print as json data
         if self.json == true {
-            println!("{}", serde_json::to_string(&module)?);
+            println!(
+                "{}",
+                serde_json::to_string(&HdlIr::from_vlog_module(&module))?
+            );
         }
 
         Ok(())
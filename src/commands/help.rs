@@ -51,6 +51,8 @@ enum Topic {
     Get,
     Tree,
     Lock,
+    Update,
+    Plan,
     Build,
     Test,
     Publish,
@@ -64,8 +66,8 @@ enum Topic {
 impl Topic {
     fn list_all() -> String {
         let list = [
-            "new", "init", "info", "read", "get", "tree", "lock", "test", "build", "publish",
-            "search", "install", "env", "config", "remove",
+            "new", "init", "info", "read", "get", "tree", "lock", "update", "plan", "test",
+            "build", "publish", "search", "install", "env", "config", "remove",
         ];
         list.into_iter().fold(String::new(), |mut acc, x| {
             acc.push_str(&format!("{}\n", x));
@@ -85,6 +87,8 @@ impl std::str::FromStr for Topic {
             "get" => Self::Get,
             "tree" => Self::Tree,
             "lock" => Self::Lock,
+            "update" => Self::Update,
+            "plan" => Self::Plan,
             "test" => Self::Test,
             "build" => Self::Build,
             "publish" => Self::Publish,
@@ -110,6 +114,8 @@ impl Topic {
             Get => manuals::get::MANUAL,
             Tree => manuals::tree::MANUAL,
             Lock => manuals::lock::MANUAL,
+            Update => manuals::update::MANUAL,
+            Plan => manuals::plan::MANUAL,
             Test => manuals::test::MANUAL,
             Build => manuals::build::MANUAL,
             Publish => manuals::publish::MANUAL,
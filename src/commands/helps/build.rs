@@ -23,17 +23,27 @@ Usage:
 
 Options:
     --target, -t <name>   target to execute
-    --top <unit>          set the top level design unit
+    --top <unit>...       set the top level design unit(s)
     --plan <format>       set the blueprint file format
     --target-dir <dir>    the relative directory where the target starts
     --command <path>      overwrite the target's command
+    --from-step <name>    start running the target's steps at this step
+    --to-step <name>      stop running the target's steps after this step
     --list                view available targets and exit
     --all                 include all hdl files of the working ip
+    --explain             report why each hdl file is included in the blueprint
     --fileset <key=glob>...
                           a glob-style pattern identified by name to include in the blueprint
+    --generic <name=value>...
+                          override a generic of the top level
     --force               force the target to execute 
+    --incremental         skip the target when its inputs are unchanged
     --no-clean            do not clean the target folder before execution
+    --watch               run again every time the ip's files change
+    --emit-script         write a shell script that reproduces the run
     --verbose             display the command being executed
+    --log                 also write the target's output to a log file
+    --quiet               only write the target's output to a log file
     args                  arguments to pass to the target
 
 Use 'orbit help build' to read more about the command."#;
@@ -25,9 +25,11 @@ pub mod install;
 pub mod lock;
 pub mod new;
 pub mod orbit;
+pub mod plan;
 pub mod publish;
 pub mod read;
 pub mod remove;
 pub mod search;
 pub mod test;
 pub mod tree;
+pub mod update;
@@ -29,6 +29,8 @@ Commands:
     get                   fetch an hdl unit for code integration
     tree                  show the dependency graph
     lock                  save the world state of an ip
+    update                refresh the locked versions of dependencies
+    plan                  prepare a target without executing it
     test, t               run a test
     build, b              plan and execute a target
     publish               post an ip to a channel
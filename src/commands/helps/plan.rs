@@ -0,0 +1,39 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// Automatically generated from the mansync.py script.
+pub const HELP: &str = r#"Prepare a target without executing it.
+
+Usage:
+    orbit plan [options]
+
+Options:
+    --target, -t <name>   target to plan for
+    --top <unit>...       set the top level design unit(s)
+    --tb <unit>...        set the top level testbench unit(s)
+    --plan <format>       set the blueprint file format
+    --target-dir <dir>    the relative directory where the target starts
+    --list                view available targets and exit
+    --all                 include all hdl files of the working ip
+    --explain             report why each hdl file is included in the blueprint
+    --fileset <key=glob>...
+                          a glob-style pattern identified by name to include in the blueprint
+    --lock-only           only update the lockfile and exit
+    --no-clean            do not clean the target folder before planning
+    --force               ignore the lockfile when resolving dependencies
+
+Use 'orbit help plan' to read more about the command."#;
@@ -19,22 +19,33 @@
 pub const HELP: &str = r#"Run a test.
 
 Usage:
-    orbit test [options] [--] [args]...
+    orbit test [options] [<glob>...] [--] [args]...
 
 Options:
     --target, -t <name>   target to execute
     --dut <unit>          set the device under test
-    --tb <unit>           set the top level testbench unit
+    --tb <unit>...        set the top level testbench unit(s)
+    --all-benches         run the target for every testbench
+    --jobs, -j <n>        number of testbenches to run at the same time with --all-benches
     --plan <format>       set the blueprint file format
     --target-dir <dir>    the relative directory where the target starts
     --command <path>      overwrite the target's command
+    --from-step <name>    start running the target's steps at this step
+    --to-step <name>      stop running the target's steps after this step
     --list                view available targets and exit
     --all                 include all hdl files of the working ip
+    --explain             report why each hdl file is included in the blueprint
     --fileset <key=glob>...
                           a glob-style pattern identified by name to include in the blueprint
+    --generic <name=value>...
+                          override a generic of the testbench
     --no-clean            do not clean the target folder before execution
+    --watch               run again every time the ip's files change
     --force               force the target to execute 
     --verbose             display the command being executed
+    --log                 also write the target's output to a log file
+    --quiet               only write the target's output to a log file
+    <glob>...             only run the testbenches matching a pattern with --all-benches
     args                  arguments to pass to the target
 
 Use 'orbit help test' to read more about the command."#;
@@ -20,7 +20,6 @@ use crate::core::catalog::Catalog;
 use crate::core::context::Context;
 use crate::core::ip::{Ip, PartialIpSpec};
 use crate::core::lang::LangUnit;
-use crate::core::version;
 use crate::core::visibility::Visibility;
 use crate::error::{Error, Hint};
 use crate::util::anyerror::AnyError;
@@ -134,11 +133,7 @@ impl Subcommand<Context> for Info {
 
         // display all installed versions in the cache
         if self.versions == true {
-            let specified_ver = if let Some(spec) = self.ip.as_ref() {
-                spec.get_version().as_specific()
-            } else {
-                None
-            };
+            let specified_ver = self.ip.as_ref().map(|spec| spec.get_version());
 
             return match catalog.get_possible_versions(ip.get_uuid()) {
                 Some(vers) => {
@@ -152,10 +147,7 @@ impl Subcommand<Context> for Info {
                             vers.iter()
                                 .filter(move |p| {
                                     specified_ver.is_none()
-                                        || version::is_compatible(
-                                            specified_ver.unwrap(),
-                                            &p.get_version(),
-                                        ) == true
+                                        || specified_ver.unwrap().matches(&p.get_version()) == true
                                 })
                                 .for_each(|v| {
                                     data.push_str(&format!(
@@ -55,7 +55,6 @@ use crate::core::protocol::Protocol;
 use crate::core::protocol::ProtocolError;
 use crate::core::source::Source;
 use crate::core::swap::StrSwapTable;
-use crate::core::version;
 use crate::core::version::AnyVersion;
 use crate::error::Error;
 use crate::error::Hint;
@@ -211,11 +210,9 @@ impl Subcommand<Context> for Install {
                     true => {
                         let ip = Ip::load(search_dir.to_path_buf(), true, false)?;
                         if ip.get_man().get_ip().get_name() == entry.get_name()
-                            && (entry.get_version().is_latest()
-                                || version::is_compatible(
-                                    entry.get_version().as_specific().unwrap(),
-                                    ip.get_man().get_ip().get_version(),
-                                ))
+                            && entry
+                                .get_version()
+                                .matches(ip.get_man().get_ip().get_version())
                         {
                             ip
                         } else {
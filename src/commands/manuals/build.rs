@@ -30,14 +30,17 @@ DESCRIPTION
     end of their execution process. This command does not allow the top to be a
     testbench, if you want to set a testbench, see 'orbit test'.
     
+    The '--top' option can be repeated to select multiple top level design units.
+    The files required by each unit are merged into a single ordered blueprint.
+    
     A target must be provided for the build command to run. A default target can
     be specified in a configuration file, which will be used when a target is
     omitted from the command-line.
     
     If '--list' is used, then it will display a list of the available targets to
     the user. Using '--list' in combination with a target from '--target' will
-    display any detailed help information the target has documented in its 
-    definition.
+    display the target's fully resolved definition, including any fields it
+    inherits from the targets it extends.
     
     A target typically goes through three steps for the building process:  
        1. Parse the blueprint file  
@@ -51,13 +54,57 @@ DESCRIPTION
     
     The target's process will spawn from the current working ip's output directory,
     which is $ORBIT_TARGET_DIR/$ORBIT_TARGET.
+    
+    A target that defines steps runs each step in order and stops at the first
+    step that fails, reporting the step's name and exit code. Use '--from-step' and
+    '--to-step' to only run part of the steps by name. The output directory is not
+    cleaned when using '--from-step', so the outputs of the earlier steps remain
+    available. Steps cannot be combined with '--command' or arguments after '--'.
+    
+    If '--incremental' is used, the target is only executed when its inputs changed
+    since the last successful build in the same output directory. The inputs are
+    the blueprint, the contents of every file listed in the blueprint, the target's
+    definition, the command-line arguments, and the environment variables passed to
+    the target. When nothing changed, the build is reported as fresh and the target
+    is skipped. Otherwise, the output directory is cleaned before the target runs,
+    keeping the newly planned blueprint and environment file along with any script
+    or explanation written by '--emit-script' or '--explain'. Use '--force' to
+    execute the target regardless.
+    
+    If '--log' is used, the output of the target's processes is also written to a
+    timestamped log file in the output directory's 'logs' folder while still being
+    displayed. Each log starts with a header listing the command, its arguments,
+    and the orbit environment variables passed to the process. Using '--quiet'
+    writes the output only to the log file, and displays the end of the log if the
+    target fails. Logs are kept when the output directory is cleaned.
+    
+    The '--generic' option overrides a generic (or parameter) of the top level by
+    name. Each name is checked against the top level's interface, and a misspelled
+    name suggests the closest declared generic. The generics are passed to the
+    target through the $ORBIT_GENERICS environment variable as a json object and
+    are written into the blueprint. The target is responsible for applying them.
+    
+    If '--watch' is used, the command keeps running after the first build and
+    runs again every time a file of the working ip or one of its relative-path
+    dependencies changes. The files are checked for changes twice a second, and a
+    burst of changes only causes a single run. A separator is displayed before each
+    run and its result is displayed after it. A failed run does not stop watching.
+    Press ctrl+c to stop.
+    
+    If '--emit-script' is used, a shell script named 'reproduce.sh' is written to the
+    output directory before the target is executed. The script exports the same
+    orbit and configuration environment variables passed to the target, enters the
+    output directory, and runs the target's resolved command and arguments (or its
+    selected steps). It can reproduce the run on a machine without orbit installed
+    as long as the output directory and the hdl files are at the same paths. The
+    target's timeouts and retries are not part of the script.
 
 OPTIONS
     --target, -t <name>
         Target to execute
 
-    --top <unit>
-        Set the top level design unit
+    --top <unit>...
+        Set the top level design unit(s)
 
     --plan <format>
         Set the blueprint file format
@@ -68,24 +115,51 @@ OPTIONS
     --command <path>
         Overwrite the target's command
 
+    --from-step <name>
+        Start running the target's steps at this step
+
+    --to-step <name>
+        Stop running the target's steps after this step
+
     --list
         View available targets and exit
 
     --all
         Include all hdl files of the working ip
 
+    --explain
+        Report why each hdl file is included in the blueprint
+
     --fileset <key=glob>...
         A glob-style pattern identified by name to include in the blueprint
 
+    --generic <name=value>...
+        Override a generic of the top level
+
     --force
         Force the target to execute 
 
+    --incremental
+        Skip the target when its inputs are unchanged
+
     --no-clean
         Do not clean the target folder before execution
 
+    --watch
+        Run again every time the ip's files change
+
+    --emit-script
+        Write a shell script that reproduces the run
+
     --verbose
         Display the command being executed
 
+    --log
+        Also write the target's output to a log file
+
+    --quiet
+        Only write the target's output to a log file
+
     args
         Arguments to pass to the target
 
@@ -94,4 +168,11 @@ EXAMPLES
     orbit build --command python3 --target pysim
     orbit build --all --target-dir build --target ghdl
     orbit build --target xsim --force -- --help
+    orbit build --target yosys --top alu --top regfile
+    orbit build --target yosys --incremental
+    orbit build --target vivado --to-step synthesize
+    orbit build --target vivado --log
+    orbit build --target vivado --top fifo --generic WIDTH=16 --generic DEPTH=8
+    orbit build --target yosys --incremental --watch
+    orbit build --target vivado --emit-script
 "#;
@@ -41,6 +41,10 @@ DESCRIPTION
     directory that contains the manifest file or 2) a zip archive file that when 
     uncompressed, has the manifest file at the root directoy.
     
+    The version in the <ip> specification can be a version requirement, such as
+    'gates:">=1.4.2, <2"', to install the highest version that satisfies it. See
+    the '[dependencies]' section of the manifest reference for the syntax.
+    
     To remove ip from the catalog, see the 'remove' command.
 
 OPTIONS
@@ -74,6 +78,7 @@ OPTIONS
 EXAMPLES
     orbit install
     orbit install lcd_driver:2.0
+    orbit install gates:"^1.4, !=1.5.0"
     orbit install adder:1.0.0 --url https://my.adder/project.zip
     orbit install alu:2.3.7 --path ./projects/alu --force
 "#;
@@ -0,0 +1,103 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    plan - prepare a target without executing it
+
+SYNOPSIS
+    orbit plan [options]
+
+DESCRIPTION
+    This command carries out the planning phase for a given target without
+    executing the target's process. The blueprint file and '.env' file are written
+    to the target's output directory, which is $ORBIT_TARGET_DIR/$ORBIT_TARGET.
+    
+    This command is useful for external tools, such as IDE integrations and 
+    makefiles, that want to use the ordered list of files from Orbit while handling
+    the execution themselves. The variables stored in the '.env' file are the same 
+    variables that would be set for the target's process during 'orbit build' or
+    'orbit test'.
+    
+    By default, the top level unit and testbench are auto-detected according to
+    the current design hierarchy. If there are multiple candidates for a potential
+    top level or testbench, it will exit and ask the user to explicitly select
+    a candidate. To include all hdl files of the working ip, use '--all'.
+    
+    Use '--explain' to report why each hdl file is included in the blueprint. For
+    every file, the shortest chain of unit references from the selected testbench 
+    or top level is displayed. Files that are only included because they belong 
+    to another unit's set of files, or because of '--all', are reported as such.
+    The report is also written as json to "explain.json" next to the blueprint.
+    
+    Targets may have custom filesets defined in their configuration, which are 
+    collected into the blueprint. Use '--fileset' as many times as needed to define
+    additional filesets.
+    
+    If only needing to update the lockfile, use '--lock-only'. This flag does 
+    not require a top level or testbench to be determined. Using '--lock-only' with
+    '--force' will overwrite the lockfile regardless if it is already in sync 
+    with the current working ip's manifest data.
+    
+    If '--list' is used, then it will display a list of the available targets to
+    the user. Using '--list' in combination with a target from '--target' will
+    display the target's fully resolved definition, including any fields it
+    inherits from the targets it extends.
+
+OPTIONS
+    --target, -t <name>
+        Target to plan for
+
+    --top <unit>...
+        Set the top level design unit(s)
+
+    --tb <unit>...
+        Set the top level testbench unit(s)
+
+    --plan <format>
+        Set the blueprint file format
+
+    --target-dir <dir>
+        The relative directory where the target starts
+
+    --list
+        View available targets and exit
+
+    --all
+        Include all hdl files of the working ip
+
+    --explain
+        Report why each hdl file is included in the blueprint
+
+    --fileset <key=glob>...
+        A glob-style pattern identified by name to include in the blueprint
+
+    --lock-only
+        Only update the lockfile and exit
+
+    --no-clean
+        Do not clean the target folder before planning
+
+    --force
+        Ignore the lockfile when resolving dependencies
+
+EXAMPLES
+    orbit plan --target ghdl
+    orbit plan --target vivado --top top --plan json
+    orbit plan --target ghdl --tb alu_tb --explain
+    orbit plan --lock-only --force
+"#;
@@ -20,7 +20,7 @@ pub const MANUAL: &str = r#"NAME
     test - run a test
 
 SYNOPSIS
-    orbit test [options] [--] [args]...
+    orbit test [options] [<glob>...] [--] [args]...
 
 DESCRIPTION
     This command prepares a given target and then executes the target.
@@ -30,14 +30,38 @@ DESCRIPTION
     "pass" or "fail", typically through a return code. This command requires a
     testbench, if you do not want to set a testbench, see 'orbit build'.
     
+    The '--tb' option can be repeated to select multiple testbenches. The files
+    required by each testbench are merged into a single ordered blueprint.
+    
+    If '--all-benches' is used, the target runs once for every testbench found in
+    the working ip instead. The testbenches can be narrowed down by name with 
+    glob-style patterns. Each testbench is planned into its own output directory,
+    which is $ORBIT_TARGET_DIR/$ORBIT_TARGET-<testbench>. After every testbench runs,
+    a summary of each testbench's result and duration is displayed. The command
+    fails if any testbench fails.
+    
+    Use '--jobs' with '--all-benches' to run up to <n> testbenches at the same time.
+    The testbenches are still planned one at a time. The output of each testbench's
+    target is written to a log file in its output directory and displayed all at
+    once when the target finishes, so the outputs of different testbenches do not
+    mix together.
+    
+    If the target defines a 'pass-pattern' or 'fail-pattern', the output of the
+    target's processes is captured while it is displayed and each line is checked
+    against the patterns to decide if the test passed. Otherwise, the output is only
+    captured when it is written to a log file. After the tests finish, a JUnit XML
+    report is written to 'junit.xml' in the target's output directory. The report
+    has a test case for every testbench with its duration, verdict, failure
+    message, and the end of its output when it was captured.
+    
     A target must be provided for the test command to run. A default target can
     be specified in a configuration file, which will be used when a target is
     omitted from the command-line.
     
     If '--list' is used, then it will display a list of the available targets to
     the user. Using '--list' in combination with a target from '--target' will
-    display any detailed help information the target has documented in its 
-    definition.
+    display the target's fully resolved definition, including any fields it
+    inherits from the targets it extends.
     
     A target typically goes through three steps for the testing process:  
        1. Parse the blueprint file  
@@ -51,6 +75,32 @@ DESCRIPTION
     
     The target's process will spawn from the current working ip's output directory,
     which is $ORBIT_TARGET_DIR/$ORBIT_TARGET.
+    
+    A target that defines steps runs each step in order and stops at the first
+    step that fails, reporting the step's name and exit code. Use '--from-step' and
+    '--to-step' to only run part of the steps by name. The output directory is not
+    cleaned when using '--from-step', so the outputs of the earlier steps remain
+    available. Steps cannot be combined with '--command' or arguments after '--'.
+    
+    If '--log' is used, the output of the target's processes is also written to a
+    timestamped log file in the output directory's 'logs' folder while still being
+    displayed. Each log starts with a header listing the command, its arguments,
+    and the orbit environment variables passed to the process. Using '--quiet'
+    writes the output only to the log file, and displays the end of the log if the
+    target fails. Logs are kept when the output directory is cleaned.
+    
+    The '--generic' option overrides a generic (or parameter) of the testbench by
+    name. Each name is checked against the testbench's interface, and a misspelled
+    name suggests the closest declared generic. The generics are passed to the
+    target through the $ORBIT_GENERICS environment variable as a json object and
+    are written into the blueprint. The target is responsible for applying them.
+    
+    If '--watch' is used, the command keeps running after the first test and
+    runs again every time a file of the working ip or one of its relative-path
+    dependencies changes. The files are checked for changes twice a second, and a
+    burst of changes only causes a single run. A separator is displayed before each
+    run and its result is displayed after it. A failed run does not stop watching.
+    Press ctrl+c to stop.
 
 OPTIONS
     --target, -t <name>
@@ -59,8 +109,14 @@ OPTIONS
     --dut <unit>
         Set the device under test
 
-    --tb <unit>
-        Set the top level testbench unit
+    --tb <unit>...
+        Set the top level testbench unit(s)
+
+    --all-benches
+        Run the target for every testbench
+
+    --jobs, -j <n>
+        Number of testbenches to run at the same time with --all-benches
 
     --plan <format>
         Set the blueprint file format
@@ -71,27 +127,58 @@ OPTIONS
     --command <path>
         Overwrite the target's command
 
+    --from-step <name>
+        Start running the target's steps at this step
+
+    --to-step <name>
+        Stop running the target's steps after this step
+
     --list
         View available targets and exit
 
     --all
         Include all hdl files of the working ip
 
+    --explain
+        Report why each hdl file is included in the blueprint
+
     --fileset <key=glob>...
         A glob-style pattern identified by name to include in the blueprint
 
+    --generic <name=value>...
+        Override a generic of the testbench
+
     --no-clean
         Do not clean the target folder before execution
 
+    --watch
+        Run again every time the ip's files change
+
     --force
         Force the target to execute 
 
     --verbose
         Display the command being executed
 
+    --log
+        Also write the target's output to a log file
+
+    --quiet
+        Only write the target's output to a log file
+
+    <glob>...
+        Only run the testbenches matching a pattern with --all-benches
+
     args
         Arguments to pass to the target
 
 EXAMPLES
     orbit test --dut adder --tb adder_tb --target modelsim -- --lint
+    orbit test --tb adder_tb --tb mult_tb --target modelsim
+    orbit test --tb adder_tb --target ghdl --from-step simulate
+    orbit test --target ghdl --quiet
+    orbit test --all-benches "alu_*" --target ghdl
+    orbit test --all-benches --target ghdl -j 4
+    orbit test --tb fifo_tb --target ghdl --generic WIDTH=16
+    orbit test --tb alu_tb --target ghdl --watch
 "#;
@@ -0,0 +1,55 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    update - refresh the locked versions of dependencies
+
+SYNOPSIS
+    orbit update [options] [<ip>...]
+
+DESCRIPTION
+    Updates the local ip's lock file, "Orbit.lock", to use the newest versions of
+    its dependencies. Each dependency is resolved to the highest version known to 
+    the catalog that still satisfies the version requirement listed in the local 
+    ip's manifest, "Orbit.toml".
+    
+    A local ip must exist for this command to execute.
+    
+    By default, every dependency is updated. If one or more ip names are given 
+    using '<ip>', then only those dependencies (along with the ip they depend on) 
+    are updated and all other dependencies keep their locked versions. Each name 
+    must be a dependency listed in the manifest of the local ip or one of its 
+    relative dependencies.
+    
+    Any newly selected versions that are only available in a channel or the 
+    downloads are downloaded and installed to the cache. After writing the lock 
+    file, a table displays the version of each dependency before and after the 
+    update.
+    
+    To resolve missing dependencies without changing their locked versions, see the
+    'lock' command.
+
+OPTIONS
+    <ip>...
+        Names of the dependencies to update
+
+EXAMPLES
+    orbit update
+    orbit update gates
+    orbit update gates adders
+"#;
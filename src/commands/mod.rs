@@ -36,6 +36,7 @@ mod remove;
 mod search;
 mod test;
 mod tree;
+mod update;
 
 // informational content for help about commands
 mod helps;
@@ -23,6 +23,7 @@ use crate::util::anyerror::AnyError;
 use crate::util::environment;
 use crate::util::prompt;
 use crate::util::sha256::Sha256Hash;
+use crate::util::subprocess;
 
 use cliproc::{cli, proc, stage::*};
 use cliproc::{Arg, Cli, Command, Subcommand};
@@ -98,6 +99,8 @@ impl Command for Orbit {
     fn execute(self) -> proc::Result {
         // synchronize the coloring mode
         self.cmode.sync();
+        // forward interrupts to the child processes that orbit waits on
+        subprocess::handle_interrupts();
         // prioritize license information
         if self.license == true {
             println!("{}", DISCLAIMER);
@@ -170,12 +173,14 @@ use crate::commands::init::Init;
 use crate::commands::install::Install;
 use crate::commands::lock::Lock;
 use crate::commands::new::New;
+use crate::commands::plan::Plan;
 use crate::commands::publish::Publish;
 use crate::commands::read::Read;
 use crate::commands::remove::Remove;
 use crate::commands::search::Search;
 use crate::commands::test::Test;
 use crate::commands::tree::Tree;
+use crate::commands::update::Update;
 
 #[derive(Debug, PartialEq)]
 enum OrbitSubcommand {
@@ -183,6 +188,8 @@ enum OrbitSubcommand {
     New(New),
     Search(Search),
     Lock(Lock),
+    Update(Update),
+    Plan(Plan),
     Build(Build),
     Test(Test),
     Publish(Publish),
@@ -201,8 +208,8 @@ impl Subcommand<Context> for OrbitSubcommand {
     fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
         match cli
             .select(&[
-                "help", "new", "search", "lock", "build", "test", "t", "publish", "install", "get",
-                "init", "tree", "info", "b", "env", "config", "remove", "read",
+                "help", "new", "search", "lock", "update", "plan", "build", "test", "t", "publish",
+                "install", "get", "init", "tree", "info", "b", "env", "config", "remove", "read",
             ])?
             .as_ref()
         {
@@ -211,6 +218,8 @@ impl Subcommand<Context> for OrbitSubcommand {
             "new" => Ok(OrbitSubcommand::New(New::interpret(cli)?)),
             "search" => Ok(OrbitSubcommand::Search(Search::interpret(cli)?)),
             "lock" => Ok(OrbitSubcommand::Lock(Lock::interpret(cli)?)),
+            "update" => Ok(OrbitSubcommand::Update(Update::interpret(cli)?)),
+            "plan" => Ok(OrbitSubcommand::Plan(Plan::interpret(cli)?)),
             "b" | "build" => Ok(OrbitSubcommand::Build(Build::interpret(cli)?)),
             "t" | "test" => Ok(OrbitSubcommand::Test(Test::interpret(cli)?)),
             "init" => Ok(OrbitSubcommand::Init(Init::interpret(cli)?)),
@@ -231,6 +240,8 @@ impl Subcommand<Context> for OrbitSubcommand {
             OrbitSubcommand::Get(sub) => sub.execute(context),
             OrbitSubcommand::Search(sub) => sub.execute(context),
             OrbitSubcommand::Lock(sub) => sub.execute(context),
+            OrbitSubcommand::Update(sub) => sub.execute(context),
+            OrbitSubcommand::Plan(sub) => sub.execute(context),
             OrbitSubcommand::Build(sub) => sub.execute(context),
             OrbitSubcommand::Install(sub) => sub.execute(context),
             OrbitSubcommand::Help(sub) => sub.execute(&()),
@@ -16,22 +16,28 @@
 //
 
 use crate::commands::download::Download;
-use crate::core::blueprint::{Blueprint, Instruction, Scheme};
+use crate::commands::helps::plan;
+use crate::core::artifacts;
+use crate::core::blueprint::{Blueprint, Class, Instruction, Library, Scheme, Unit};
 use crate::core::context::{self, Context};
+use crate::core::explain::{self, Explain, Explanation, Reason};
 use crate::core::fileset::Fileset;
+use crate::core::generic::{self, Generic};
 use crate::core::iparchive::IpArchive;
+use crate::core::lang::cache::{self, SymbolCache};
+use crate::core::lang::ir::HdlIr;
 use crate::core::lang::parser::ParseError;
 use crate::core::lang::reference::CompoundIdentifier;
-use crate::core::lang::sv::symbols::{SystemVerilogParser, SystemVerilogSymbol};
-use crate::core::lang::verilog::symbols::{VerilogParser, VerilogSymbol};
+use crate::core::lang::sv::symbols::SystemVerilogSymbol;
+use crate::core::lang::verilog::symbols::VerilogSymbol;
 use crate::core::lang::vhdl::subunit::SubUnit;
-use crate::core::lang::vhdl::symbols::{VHDLParser, VhdlSymbol};
+use crate::core::lang::vhdl::symbols::VhdlSymbol;
 use crate::core::lang::vhdl::token::Identifier;
 use crate::core::lang::{self, Lang, LangIdentifier};
 use crate::core::swap;
 use crate::core::swap::StrSwapTable;
 use crate::core::target::Target;
-use crate::core::version::AnyVersion;
+use crate::core::version::{AnyVersion, Version};
 use crate::error::{Error, Hint, LastError};
 use crate::util::anyerror::Fault;
 use crate::util::environment;
@@ -40,10 +46,14 @@ use crate::util::environment::Environment;
 use crate::util::filesystem;
 use crate::util::graph::EdgeStatus;
 use crate::util::graphmap::GraphMap;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::fs;
 use std::hash::Hash;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
 
 use crate::commands::install::Install;
 use crate::core::algo;
@@ -59,42 +69,174 @@ use crate::util::graphmap::Node;
 #[derive(Debug, PartialEq)]
 pub struct Plan {
     target: Option<String>,
-    bench: Option<Identifier>,
-    top: Option<Identifier>,
+    bench: Option<Vec<Identifier>>,
+    top: Option<Vec<Identifier>>,
+    plan: Option<Scheme>,
     clean: bool,
     list: bool,
     all: bool,
+    explain: bool,
     target_dir: Option<String>,
     filesets: Option<Vec<Fileset>>,
     only_lock: bool,
     force: bool,
 }
 
+impl Subcommand<Context> for Plan {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(plan::HELP))?;
+        Ok(Plan {
+            // Flags
+            list: cli.check(Arg::flag("list"))?,
+            all: cli.check(Arg::flag("all"))?,
+            explain: cli.check(Arg::flag("explain"))?,
+            only_lock: cli.check(Arg::flag("lock-only"))?,
+            clean: cli.check(Arg::flag("no-clean"))? == false,
+            force: cli.check(Arg::flag("force"))?,
+            // Options
+            top: cli.get_all(Arg::option("top").value("unit"))?,
+            bench: cli.get_all(Arg::option("tb").value("unit"))?,
+            plan: cli.get(Arg::option("plan").value("format"))?,
+            target: cli.get(Arg::option("target").value("name").switch('t'))?,
+            target_dir: cli.get(Arg::option("target-dir").value("dir"))?,
+            filesets: cli.get_all(Arg::option("fileset").value("key=glob"))?,
+        })
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // locate the target provided from the command-line
+        let target = c.select_target(
+            &self.target,
+            self.list == false && self.only_lock == false,
+            true,
+        )?;
+
+        // display targets list and exit
+        if self.list == true {
+            // try to get the default target
+            let def_target = c.select_target(&None, true, true).unwrap_or(None);
+            match target {
+                // display entire contents about the particular target
+                Some(tg) => println!("{}", tg),
+                // display quick overview of all targets
+                None => print!(
+                    "{}",
+                    Target::list_targets(
+                        &mut c
+                            .get_config()
+                            .get_targets()
+                            .values()
+                            .into_iter()
+                            .collect::<Vec<&&Target>>(),
+                        def_target.as_ref(),
+                    )
+                ),
+            }
+            return Ok(());
+        }
+
+        // check that user is in an IP directory
+        c.jump_to_working_ip()?;
+
+        // store the working ip struct
+        let mut working_ip = Ip::load(c.get_ip_path().unwrap().clone(), true, false)?;
+
+        // determine the build directory (command-line arg overrides configuration setting)
+        let default_target_dir = c.get_target_dir();
+        let target_dir = self.target_dir.as_ref().unwrap_or(&default_target_dir);
+
+        // reuse the symbols parsed for the working ip during previous plans
+        let symbols = SymbolCache::open(&working_ip.get_root().join(target_dir));
+        working_ip.set_symbol_cache(symbols);
+
+        // gather the catalog and resolve any missing dependencies
+        let catalog = Catalog::new()
+            .installations(c.get_cache_path())?
+            .downloads(c.get_downloads_path())?;
+        let catalog = resolve_missing_deps(c, &working_ip, catalog, self.force)?;
+
+        // only update the lockfile (a target is not required)
+        if self.only_lock == true {
+            let ip_graph = match algo::compute_final_ip_graph(&working_ip, &catalog) {
+                Ok(g) => g,
+                Err(e) => return Err(Error::IpGraphFailed(LastError(e.into_fault().to_string())))?,
+            };
+            return Ok(Self::write_lockfile(
+                &working_ip,
+                &ip_graph,
+                self.force,
+                true,
+                &catalog,
+            )?);
+        }
+
+        // unwrap because at this point the target must exist
+        let target = target.unwrap();
+
+        // coordinate the plan
+        let scheme = target.coordinate_plan(&self.plan)?;
+
+        let envs = Environment::new()
+            // read config.toml for setting any env variables
+            .from_config(c.get_config())?
+            // read ip manifest for env variables
+            .from_ip(&working_ip)?
+            .add(EnvVar::with(environment::ORBIT_TARGET_DIR, target_dir))
+            .add(EnvVar::with(environment::ORBIT_OUT_DIR, target.get_name()));
+
+        Self::run(
+            &working_ip,
+            target_dir,
+            target.get_name(),
+            &target,
+            &catalog,
+            self.clean,
+            self.force,
+            false,
+            self.all,
+            self.explain,
+            self.bench.as_deref().unwrap_or(&[]),
+            self.top.as_deref().unwrap_or(&[]),
+            &self.filesets,
+            &[],
+            &scheme,
+            false,
+            true,
+            envs,
+        )?;
+        Ok(())
+    }
+}
+
 impl Plan {
     /// Performs the backend logic for creating a blueprint file (planning a design).
     ///
-    /// If a blueprint was created, it will return the file name for that blueprint.
+    /// If a blueprint was created, it will return the file name for that blueprint
+    /// along with the paths of the files listed in the blueprint.
     pub fn run(
         working_ip: &Ip,
         target_dir: &str,
+        out_dir: &str,
         target: &Target,
-        catalog: Catalog,
+        catalog: &Catalog,
         clean: bool,
         force: bool,
         only_lock: bool,
         all: bool,
-        bench_name: &Option<Identifier>,
-        top_name: &Option<Identifier>,
+        explain: bool,
+        bench_names: &[Identifier],
+        top_names: &[Identifier],
         filesets: &Option<Vec<Fileset>>,
+        generics: &[Generic],
         scheme: &Scheme,
         require_bench: bool,
         allow_bench: bool,
         envs: Environment,
-    ) -> Result<Option<String>, Fault> {
+    ) -> Result<Option<(String, Vec<String>)>, Fault> {
         // create the output path to know where to begin storing files
         let working_ip_path = working_ip.get_root().clone();
         let target_path = working_ip_path.join(target_dir);
-        let output_path = target_path.join(target.get_name());
+        let output_path = target_path.join(out_dir);
 
         // build entire ip graph and resolve with dynamic symbol transformation
         let ip_graph = match algo::compute_final_ip_graph(&working_ip, &catalog) {
@@ -108,19 +250,27 @@ impl Plan {
                         &working_ip,
                         LangIdentifier::new_working(),
                     );
-                    blueprint.add(Instruction::Hdl(&ip_file_node));
+                    // no graph exists to know which units reference the file
+                    let class = match fileset::is_rtl(ip_file_node.get_file()) {
+                        true => Class::Rtl,
+                        false => Class::Sim,
+                    };
+                    blueprint.add(Instruction::Hdl(&ip_file_node, class));
 
                     let blueprint_name = blueprint.get_filename();
                     let blueprint_path = Self::create_outputs(
                         &blueprint,
                         envs,
                         &target_path,
+                        out_dir,
                         &String::new(),
                         &String::new(),
                         &String::new(),
+                        "[]",
                         &String::new(),
                         &String::new(),
                         &String::new(),
+                        "[]",
                         target,
                         require_bench,
                     )?;
@@ -129,7 +279,7 @@ impl Plan {
                         "erroneous blueprint created at {:?}",
                         filesystem::into_std_str(blueprint_path)
                     );
-                    return Ok(Some(blueprint_name));
+                    return Ok(Some((blueprint_name, blueprint.get_files())));
                 } else {
                     return match e.is_source_err() {
                         true => Err(Error::SourceCodeInvalidSyntax(
@@ -148,21 +298,32 @@ impl Plan {
             return Ok(None);
         }
 
-        // check if to clean the directory
+        // check if to clean the directory (collected artifacts are kept)
         if clean == true && Path::exists(&output_path) == true {
-            fs::remove_dir_all(&output_path)?;
+            artifacts::clean_output(&output_path, &[])?;
         }
 
         let files = algo::build_ip_file_list(&ip_graph, &working_ip);
 
         let global_graph = Self::build_full_graph(&files)?;
 
+        // remember the parsed symbols of the working ip for the next plan
+        if let Some(symbols) = working_ip.get_symbol_cache() {
+            if let Err(e) = symbols.save() {
+                crate::warn!("failed to save the symbol cache: {}", e);
+            }
+        }
+
         let working_lib = working_ip.get_hdl_library();
 
         // restrict graph to units only found within the current IP
         let local_graph: GraphMap<&CompoundIdentifier, &HdlNode, &()> =
             Self::compute_local_graph(&global_graph, &working_ip);
 
+        // the first unit given for each role drives the automatic detection
+        let bench_name = bench_names.first().cloned();
+        let top_name = top_names.first().cloned();
+
         let (top, bench) = match allow_bench {
             true => {
                 match Self::detect_bench(
@@ -225,8 +386,9 @@ impl Plan {
 
         // error if the user-defined top is not instantiated in the testbench. Say this can be fixed by adding '--all'
         if let Some(b) = &bench {
-            // @idea: merge two topological sorted lists together by running top sort from bench and top sort from top if in this situation
             if all == false
+                && top_names.len() <= 1
+                && bench_names.len() <= 1
                 && top.is_some()
                 && global_graph
                     .get_graph()
@@ -249,9 +411,30 @@ impl Plan {
             return Err(Error::TestbenchRequired)?;
         }
 
+        // collect every selected unit, including the remaining units given by the user
+        let mut tops: Vec<usize> = top.into_iter().collect();
+        for name in top_names.iter().skip(1) {
+            let i = Self::find_unit(&local_graph, &working_lib, name, false, allow_bench)?;
+            let i = Self::local_to_global(i, &global_graph, &local_graph).index();
+            if tops.contains(&i) == false {
+                tops.push(i);
+            }
+        }
+        let mut benches: Vec<usize> = bench.into_iter().collect();
+        for name in bench_names.iter().skip(1) {
+            let i = Self::find_unit(&local_graph, &working_lib, name, true, allow_bench)?;
+            let i = Self::local_to_global(i, &global_graph, &local_graph).index();
+            if benches.contains(&i) == false {
+                benches.push(i);
+            }
+        }
+
         // [!] write the lock file
         Self::write_lockfile(&working_ip, &ip_graph, true, true, &catalog)?;
 
+        // break ties between independent units to keep the order reproducible
+        let unit_key = |i: usize| Self::unit_sort_key(&global_graph, i);
+
         // compute minimal topological ordering
         let min_order = match all {
             // perform topological sort on the entire graph
@@ -262,18 +445,22 @@ impl Plan {
                         let id = Self::local_to_global(r.index(), &global_graph, &local_graph);
                         global_graph
                             .get_graph()
-                            .minimal_topological_sort(id.index())
+                            .minimal_topological_sort_by_key(id.index(), unit_key)
                     }
                     // exclude roots that do not belong to the local graph
                     Err(roots) => {
                         // println!("{:?}", roots);
+                        let mut roots: Vec<usize> = roots
+                            .iter()
+                            .map(|r| Self::local_to_global(*r, &global_graph, &local_graph).index())
+                            .collect();
+                        roots.sort_by_key(|r| unit_key(*r));
                         let mut order = Vec::new();
                         // create dummy node to rely on all known roots
                         roots.iter().for_each(|r| {
-                            let id = Self::local_to_global(*r, &global_graph, &local_graph);
                             let mut subset = global_graph
                                 .get_graph()
-                                .minimal_topological_sort(id.index());
+                                .minimal_topological_sort_by_key(*r, unit_key);
                             order.append(&mut subset);
                         });
                         order
@@ -282,109 +469,103 @@ impl Plan {
             }
             // perform topological sort on minimal subset of the graph
             false => {
-                // determine which point is the upmost root
-                let highest_point = match bench {
-                    Some(b) => b,
-                    None => match top {
-                        Some(t) => t,
-                        None => return Err(AnyError(format!("no top-level unit exists")))?,
-                    },
-                };
-                global_graph
-                    .get_graph()
-                    .minimal_topological_sort(highest_point)
+                // determine which points are the upmost roots
+                let roots: Vec<&usize> = benches.iter().chain(tops.iter()).collect();
+                if roots.is_empty() == true {
+                    return Err(AnyError(format!("no top-level unit exists")))?;
+                }
+                // merge the topological sorts from each root together
+                let mut order = Vec::new();
+                roots.into_iter().for_each(|r| {
+                    global_graph
+                        .get_graph()
+                        .minimal_topological_sort_by_key(*r, unit_key)
+                        .into_iter()
+                        .for_each(|i| {
+                            if order.contains(&i) == false {
+                                order.push(i);
+                            }
+                        });
+                });
+                order
             }
         };
 
         // println!("{:?}", min_order);
 
         // generate the file order while merging dependencies for common file path names together
-        let file_order = Self::determine_file_order(&global_graph, min_order);
+        let file_order = Self::determine_file_order(&global_graph, min_order.clone());
 
         // remove duplicate files from list while perserving order
         let file_order = Self::remove_multi_occurences(&file_order);
 
-        // grab the names as strings
-        let top_name = match top {
-            Some(i) => global_graph
-                .get_key_by_index(i)
-                .unwrap()
-                .get_suffix()
-                .to_string(),
-            None => String::new(),
-        };
-        let bench_name = match bench {
-            Some(i) => global_graph
-                .get_key_by_index(i)
-                .unwrap()
-                .get_suffix()
-                .to_string(),
-            None => String::new(),
-        };
+        // classify each hdl file and drop the simulation-only files when unwanted
+        let classes = Self::classify_file_order(&global_graph, &file_order, &min_order, &tops);
+        let (file_order, classes): (Vec<&&IpFileNode>, Vec<Class>) = file_order
+            .into_iter()
+            .zip(classes)
+            .filter(|(_, c)| target.is_rtl_only() == false || c == &Class::Rtl)
+            .unzip();
 
-        // grab the files that contain the top-level/dut design unit
-        let top_file = match top {
-            Some(i) => {
-                let n = global_graph.get_node_by_index(i).unwrap();
-                n.as_ref()
-                    .get_associated_files()
-                    .first()
-                    .unwrap()
-                    .get_file()
-            }
-            None => "",
-        };
+        // verify the target is able to process every hdl file
+        Self::verify_languages(target, &file_order)?;
 
-        // grab the json representation of the top level unit
-        let top_json = match top {
-            Some(i) => {
-                let n = global_graph.get_node_by_index(i).unwrap();
-                let sym = n.as_ref().get_symbol();
-                if let Some(m) = sym.as_module() {
-                    serde_json::to_string(m)?
-                } else if let Some(e) = sym.as_entity() {
-                    serde_json::to_string(e)?
-                } else {
-                    String::new()
-                }
-            }
-            None => String::new(),
-        };
+        // determine the order to compile the libraries in
+        let (libraries, cycle) = Self::order_libraries(&global_graph, &file_order, &min_order);
+        if let Some(cycle) = cycle {
+            crate::warn!("{}", cycle);
+        }
 
-        // grab the file that contains the testbench unit
-        let bench_file = match bench {
-            Some(i) => {
-                let n = global_graph.get_node_by_index(i).unwrap();
-                n.as_ref()
-                    .get_associated_files()
-                    .first()
-                    .unwrap()
-                    .get_file()
-            }
-            None => "",
+        // determine why each hdl file is written to the blueprint
+        let explanation = match explain {
+            true => Some(Self::explain_file_order(
+                &global_graph,
+                &file_order,
+                &min_order,
+                &benches,
+                &tops,
+            )),
+            false => None,
         };
 
-        // grab the json representation of the testbench unit
-        let bench_json = match bench {
-            Some(i) => {
-                let n = global_graph.get_node_by_index(i).unwrap();
-                let sym = n.as_ref().get_symbol();
-                if let Some(m) = sym.as_module() {
-                    serde_json::to_string(m)?
-                } else if let Some(e) = sym.as_entity() {
-                    serde_json::to_string(e)?
-                } else {
-                    String::new()
-                }
+        // grab the name, file, and json representation of each selected unit
+        let top_units = tops
+            .iter()
+            .map(|i| Self::get_unit_info(&global_graph, *i))
+            .collect::<Result<Vec<(String, String, String)>, Fault>>()?;
+        let bench_units = benches
+            .iter()
+            .map(|i| Self::get_unit_info(&global_graph, *i))
+            .collect::<Result<Vec<(String, String, String)>, Fault>>()?;
+
+        // verify the generics exist on the units they override (the testbench when testing)
+        let mut generics = generics.to_vec();
+        if generics.is_empty() == false {
+            let (units, role, hint) = match require_bench {
+                true => (&bench_units, "testbench", Hint::BenchSpecify),
+                false => (&top_units, "top-level", Hint::TopSpecify),
+            };
+            if units.is_empty() == true {
+                return Err(Error::GenericsWithoutUnit(role.to_string(), hint))?;
             }
-            None => String::new(),
-        };
+            for (_, _, json) in units.iter().filter(|(_, _, j)| j.is_empty() == false) {
+                generics = generic::verify(&generics, &HdlIr::from_str(json)?)?;
+            }
+        }
+
+        let (top_name, top_file, top_json, top_jsons) = Self::join_unit_info(&top_units);
+        let (bench_name, bench_file, bench_json, bench_jsons) = Self::join_unit_info(&bench_units);
 
         // print information (maybe also print the plugin saved to .env too?)
-        match top_name.is_empty() {
+        let top_list = top_units
+            .iter()
+            .map(|(n, _, _)| n.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ");
+        match top_list.is_empty() {
             false => match require_bench {
-                true => crate::info!("dut set to {}", top_name.blue()),
-                false => crate::info!("p-level set to {}", top_name.blue()),
+                true => crate::info!("dut set to {}", top_list.blue()),
+                false => crate::info!("top-level set to {}", top_list.blue()),
             },
             true => match require_bench {
                 true => crate::warn!("no dut set"),
@@ -392,8 +573,13 @@ impl Plan {
             },
         }
         if require_bench == true {
-            match bench_name.is_empty() {
-                false => crate::info!("testbench set to {}", bench_name.blue()),
+            let bench_list = bench_units
+                .iter()
+                .map(|(n, _, _)| n.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ");
+            match bench_list.is_empty() {
+                false => crate::info!("testbench set to {}", bench_list.blue()),
                 true => crate::warn!("no testbench set"),
             }
         }
@@ -401,18 +587,42 @@ impl Plan {
         // store data in blueprint
         let mut blueprint = Blueprint::new(scheme.clone());
 
+        blueprint.set_libraries(libraries);
+        blueprint.set_generics(generics);
+
+        // store the selected units (the top is the dut when testing)
+        let into_units = |info: &Vec<(String, String, String)>| -> Vec<Unit> {
+            info.iter()
+                .filter_map(|(n, f, j)| Unit::new(n, f, j))
+                .collect()
+        };
+        match require_bench {
+            true => {
+                blueprint.set_units(Vec::new(), into_units(&top_units), into_units(&bench_units))
+            }
+            false => {
+                blueprint.set_units(into_units(&top_units), Vec::new(), into_units(&bench_units))
+            }
+        }
+
         // [!] collect user-defined filesets
         {
             let current_files: Vec<String> = working_ip.gather_current_files();
 
             let mut vtable = StrSwapTable::new();
             // variables could potentially store empty strings if units are not set
-            vtable.add("orbit.tb.name", &bench_name);
-            vtable.add("orbit.top.name", &top_name);
-            vtable.add("orbit.dut.name", &top_name);
+            let primary_bench = bench_units
+                .first()
+                .map(|(n, _, _)| n.as_str())
+                .unwrap_or("");
+            let primary_top = top_units.first().map(|(n, _, _)| n.as_str()).unwrap_or("");
+            vtable.add("orbit.tb.name", primary_bench);
+            vtable.add("orbit.top.name", primary_top);
+            vtable.add("orbit.dut.name", primary_top);
 
             // store data in a map for quicker look-ups when comparing to plugin-defind filesets
-            let mut cli_fset_map: HashMap<&String, &Fileset> = HashMap::new();
+            // (ordered by name so the remaining filesets are written in a stable order)
+            let mut cli_fset_map: BTreeMap<&String, &Fileset> = BTreeMap::new();
 
             // use command-line set filesets
             if let Some(fsets) = filesets {
@@ -448,6 +658,7 @@ impl Plan {
                                 fset.get_name().clone(),
                                 working_lib.to_string(),
                                 f.clone(),
+                                working_ip,
                             ));
                         });
                 }
@@ -467,18 +678,15 @@ impl Plan {
                             fset.get_name().clone(),
                             working_lib.to_string(),
                             f.clone(),
+                            working_ip,
                         ));
                     });
             }
         }
 
         // collect in-order HDL file list
-        for ip_file_node in file_order {
-            if fileset::is_rtl(&ip_file_node.get_file()) == true {
-                blueprint.add(Instruction::Hdl(ip_file_node));
-            } else {
-                blueprint.add(Instruction::Hdl(ip_file_node));
-            }
+        for (ip_file_node, class) in file_order.into_iter().zip(classes) {
+            blueprint.add(Instruction::Hdl(ip_file_node, class));
         }
 
         let blueprint_name = blueprint.get_filename();
@@ -487,12 +695,15 @@ impl Plan {
             &blueprint,
             envs,
             &target_path,
+            out_dir,
             &top_name,
             &top_file,
             &top_json,
+            &top_jsons,
             &bench_name,
             &bench_file,
             &bench_json,
+            &bench_jsons,
             target,
             require_bench,
         )?;
@@ -501,7 +712,26 @@ impl Plan {
             "blueprint created at: {:?}",
             filesystem::into_std_str(blueprint_path)
         );
-        Ok(Some(blueprint_name))
+
+        // write the explanation alongside the blueprint
+        match explanation {
+            Some(e) => {
+                print!("{}", e);
+                let explain_path = e.write(&output_path)?;
+                crate::info!(
+                    "explanation created at: {:?}",
+                    filesystem::into_std_str(explain_path)
+                );
+            }
+            None => {
+                // remove any stale explanation from a previous plan
+                let explain_path = output_path.join(explain::EXPLAIN_FILE);
+                if explain_path.exists() == true {
+                    fs::remove_file(&explain_path)?;
+                }
+            }
+        }
+        Ok(Some((blueprint_name, blueprint.get_files())))
     }
 }
 
@@ -723,8 +953,8 @@ impl Plan {
         component_pairs: &'b mut HashMap<LangIdentifier, LangIdentifier>,
     ) -> Result<(), Fault> {
         let contents = lang::read_to_string(&node.get_file())?;
-        let symbols = match VerilogParser::read(&contents) {
-            Ok(s) => s.into_symbols(),
+        let symbols = match cache::read_verilog(node.get_ip().get_symbol_cache(), &contents) {
+            Ok(s) => s,
             Err(e) => Err(ParseError::SourceCodeError(
                 node.get_file().clone(),
                 e.to_string(),
@@ -792,8 +1022,8 @@ impl Plan {
         component_pairs: &'b mut HashMap<LangIdentifier, LangIdentifier>,
     ) -> Result<(), Fault> {
         let contents = lang::read_to_string(&node.get_file())?;
-        let symbols = match SystemVerilogParser::read(&contents) {
-            Ok(s) => s.into_symbols(),
+        let symbols = match cache::read_systemverilog(node.get_ip().get_symbol_cache(), &contents) {
+            Ok(s) => s,
             Err(e) => Err(ParseError::SourceCodeError(
                 node.get_file().clone(),
                 e.to_string(),
@@ -854,8 +1084,8 @@ impl Plan {
         sub_nodes: &'b mut Vec<(LangIdentifier, SubUnitNode<'a>)>,
     ) -> Result<(), Fault> {
         let contents = lang::read_to_string(&node.get_file())?;
-        let symbols = match VHDLParser::read(&contents) {
-            Ok(s) => s.into_symbols(),
+        let symbols = match cache::read_vhdl(node.get_ip().get_symbol_cache(), &contents) {
+            Ok(s) => s,
             Err(e) => Err(ParseError::SourceCodeError(
                 node.get_file().clone(),
                 e.to_string(),
@@ -1105,12 +1335,14 @@ impl Plan {
             }
         }
 
-        // go through all nodes and make the connections
-        let idens: Vec<CompoundIdentifier> = graph_map
+        // go through all nodes and make the connections (in the order they were added)
+        let mut idens: Vec<(usize, CompoundIdentifier)> = graph_map
             .get_map()
             .into_iter()
-            .map(|(k, _)| k.clone())
+            .map(|(k, n)| (n.index(), k.clone()))
             .collect();
+        idens.sort_by_key(|(i, _)| *i);
+        let idens = idens.into_iter().map(|(_, k)| k);
         for iden in idens {
             let references: Vec<CompoundIdentifier> = if let Some(refs) = graph_map
                 .get_node_by_key(&iden)
@@ -1215,6 +1447,128 @@ impl Plan {
             .unwrap()
     }
 
+    /// Finds the local index of the unit `name` that was explicitly requested
+    /// as a testbench (`is_bench`) or as a top-level.
+    fn find_unit(
+        local: &GraphMap<&CompoundIdentifier, &HdlNode, &()>,
+        working_lib: &LangIdentifier,
+        name: &Identifier,
+        is_bench: bool,
+        allow_bench: bool,
+    ) -> Result<usize, PlanError> {
+        match local.get_node_by_key(&&CompoundIdentifier::new_vhdl(
+            working_lib.as_vhdl_name().unwrap().clone(),
+            name.clone(),
+        )) {
+            Some(node) => {
+                let sym = node.as_ref().get_symbol();
+                match is_bench {
+                    // verify the unit is an entity that is a testbench
+                    true => {
+                        if sym.is_component() == false {
+                            return Err(PlanError::BadEntity(name.clone()));
+                        } else if sym.is_testbench() == false {
+                            return Err(PlanError::BadTestbench(name.clone(), Hint::WantsTop));
+                        }
+                    }
+                    // verify the unit is not a testbench
+                    false => {
+                        if sym.is_component() == true && sym.is_testbench() == true {
+                            return Err(match allow_bench {
+                                true => PlanError::BadDut(name.clone(), Hint::BenchSpecify),
+                                false => PlanError::BadTop(name.clone(), Hint::WantsTestbench),
+                            });
+                        }
+                    }
+                }
+                Ok(node.index())
+            }
+            None => Err(PlanError::UnknownEntity(name.clone())),
+        }
+    }
+
+    /// Returns the name, file, and serialized json interface of the unit at
+    /// index `i` in the `global_graph`.
+    fn get_unit_info(
+        global_graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+        i: usize,
+    ) -> Result<(String, String, String), Fault> {
+        let name = global_graph
+            .get_key_by_index(i)
+            .unwrap()
+            .get_suffix()
+            .to_string();
+        let n = global_graph.get_node_by_index(i).unwrap();
+        let file = n
+            .as_ref()
+            .get_associated_files()
+            .first()
+            .unwrap()
+            .get_file()
+            .clone();
+        let sym = n.as_ref().get_symbol();
+        let json = if let Some(m) = sym.as_module() {
+            serde_json::to_string(&HdlIr::from_vlog_module(m))?
+        } else if let Some(e) = sym.as_entity() {
+            serde_json::to_string(&HdlIr::from_vhdl_entity(e))?
+        } else {
+            String::new()
+        };
+        Ok((name, file, json))
+    }
+
+    /// Combines the information of multiple units into single values to store
+    /// as environment variables.
+    ///
+    /// Names and files are separated by `;`. The json interface of the first
+    /// unit is returned on its own, followed by a json array of every unit's
+    /// interface.
+    fn join_unit_info(units: &Vec<(String, String, String)>) -> (String, String, String, String) {
+        let names: Vec<&str> = units.iter().map(|(n, _, _)| n.as_str()).collect();
+        let files: Vec<&str> = units.iter().map(|(_, f, _)| f.as_str()).collect();
+        let jsons: Vec<&str> = units
+            .iter()
+            .map(|(_, _, j)| j.as_str())
+            .filter(|j| j.is_empty() == false)
+            .collect();
+        (
+            names.join(environment::LIST_SEPARATOR),
+            files.join(environment::LIST_SEPARATOR),
+            jsons.first().unwrap_or(&"").to_string(),
+            format!("[{}]", jsons.join(",")),
+        )
+    }
+
+    /// Finds the name of every testbench defined within the `working_ip`.
+    ///
+    /// The names are returned in alphabetical order.
+    pub fn find_testbenches(working_ip: &Ip, catalog: &Catalog) -> Result<Vec<Identifier>, Fault> {
+        let ip_graph = match algo::compute_final_ip_graph(&working_ip, &catalog) {
+            Ok(g) => g,
+            Err(e) => match e.is_source_err() {
+                true => Err(Error::SourceCodeInvalidSyntax(
+                    e.as_source_file().unwrap().clone().into(),
+                    LastError(e.into_fault().to_string()),
+                ))?,
+                false => Err(Error::IpGraphFailed(LastError(e.into_fault().to_string())))?,
+            },
+        };
+        let files = algo::build_ip_file_list(&ip_graph, &working_ip);
+        let global_graph = Self::build_full_graph(&files)?;
+        let local_graph = Self::compute_local_graph(&global_graph, &working_ip);
+
+        let mut benches: Vec<Identifier> = local_graph
+            .get_map()
+            .values()
+            .map(|n| n.as_ref().get_symbol())
+            .filter(|sym| sym.is_component() == true && sym.is_testbench() == true)
+            .filter_map(|sym| Identifier::from_str(&sym.get_name().to_string()).ok())
+            .collect();
+        benches.sort_by_key(|name| name.to_string());
+        benches.dedup();
+        Ok(benches)
+    }
+
     fn detect_bench(
         _graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
         local: &GraphMap<&CompoundIdentifier, &HdlNode, &()>,
@@ -1592,15 +1946,381 @@ impl Plan {
                 }
             }
         }
-        // topologically sort and transform into list of the file nodes
+        // topologically sort and transform into list of the file nodes (ties are
+        // broken by each file's key)
         file_graph
             .get_graph()
-            .topological_sort()
+            .topological_sort_by_key(|i| {
+                Self::file_sort_key(file_graph.get_key_by_index(i).unwrap())
+            })
             .into_iter()
             .map(|i| *file_graph.get_key_by_index(i).unwrap())
             .collect()
     }
 
+    /// Creates the key that orders the hdl file `node` among the files that do
+    /// not depend on each other: by library, then ip, then path.
+    fn file_sort_key<'a>(node: &'a IpFileNode) -> (String, String, Version, &'a String) {
+        let ip = node.get_ip().get_man().get_ip();
+        (
+            node.get_library().to_string(),
+            ip.get_name().to_string(),
+            ip.get_version().clone(),
+            node.get_file(),
+        )
+    }
+
+    /// Creates the key that orders the unit at index `i` among the units that do
+    /// not depend on each other: by its primary file's key, then its name.
+    fn unit_sort_key<'a>(
+        global_graph: &'a GraphMap<CompoundIdentifier, HdlNode, ()>,
+        i: usize,
+    ) -> (Option<(String, String, Version, &'a String)>, String) {
+        let node = global_graph.get_node_by_index(i).unwrap().as_ref();
+        (
+            node.get_associated_files()
+                .first()
+                .map(|f| Self::file_sort_key(f)),
+            global_graph.get_key_by_index(i).unwrap().to_string(),
+        )
+    }
+
+    /// Classifies each hdl file in the `file_order` as rtl or simulation-only.
+    ///
+    /// A unit is rtl when it is a selected top-level unit (the dut when testing),
+    /// a unit that is neither a testbench nor referenced by another planned unit,
+    /// or a dependency of an rtl unit. Every other unit is only reachable from a
+    /// testbench. When no top-level unit is selected, the units only referenced
+    /// by testbenches are treated as top-level units. A file is rtl if any of its
+    /// associated units are rtl.
+    fn classify_file_order(
+        global_graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+        file_order: &Vec<&&IpFileNode>,
+        min_order: &Vec<usize>,
+        tops: &Vec<usize>,
+    ) -> Vec<Class> {
+        let is_testbench = |i: usize| {
+            global_graph
+                .get_node_by_index(i)
+                .unwrap()
+                .as_ref()
+                .get_symbol()
+                .is_testbench()
+        };
+        let planned: HashSet<&usize> = min_order.iter().collect();
+        // collect the units that are roots of the rtl design
+        let mut queue: VecDeque<usize> = min_order
+            .iter()
+            .filter(|i| {
+                is_testbench(**i) == false
+                    && global_graph
+                        .get_graph()
+                        .successors(**i)
+                        .filter(|s| planned.contains(s))
+                        .find(|s| tops.is_empty() == false || is_testbench(*s) == false)
+                        .is_none()
+            })
+            .chain(tops.iter())
+            .cloned()
+            .collect();
+        let mut rtl: HashSet<usize> = queue.iter().cloned().collect();
+        // every dependency of a rtl unit is also rtl
+        while let Some(n) = queue.pop_front() {
+            for p in global_graph.get_graph().predecessors(n) {
+                if rtl.insert(p) == true {
+                    queue.push_back(p);
+                }
+            }
+        }
+
+        let rtl_files: HashSet<&String> = rtl
+            .iter()
+            .flat_map(|i| {
+                global_graph
+                    .get_node_by_index(*i)
+                    .unwrap()
+                    .as_ref()
+                    .get_associated_files()
+                    .iter()
+                    .map(|f| f.get_file())
+            })
+            .collect();
+        file_order
+            .iter()
+            .map(|f| match rtl_files.contains(f.get_file()) {
+                true => Class::Rtl,
+                false => Class::Sim,
+            })
+            .collect()
+    }
+
+    /// Checks that the `target` supports the language and standard of every hdl
+    /// file in the `file_order`.
+    ///
+    /// The error lists each unsupported file along with the ip it belongs to.
+    fn verify_languages(target: &Target, file_order: &Vec<&&IpFileNode>) -> Result<(), Error> {
+        let supported = match target.get_languages() {
+            Some(langs) => langs,
+            None => return Ok(()),
+        };
+        let mut unsupported = Vec::new();
+        for ip_file_node in file_order {
+            let ip = ip_file_node.get_ip();
+            let lang = ip_file_node.get_language();
+            let std = ip
+                .get_file_attributes(ip_file_node.get_file())
+                .get_std()
+                .clone();
+            if target.supports_language(lang, &std) == false {
+                let file =
+                    filesystem::remove_base(ip.get_root(), &PathBuf::from(ip_file_node.get_file()));
+                unsupported.push(format!(
+                    "    {} ({}) - {}{}",
+                    filesystem::into_std_str(file),
+                    ip.get_man().get_ip().into_ip_spec(),
+                    lang,
+                    std.map(|s| format!(":{}", s)).unwrap_or_default()
+                ));
+            }
+        }
+        match unsupported.is_empty() {
+            true => Ok(()),
+            false => Err(Error::TargetLanguageNotSupported(
+                target.get_name().to_string(),
+                supported
+                    .iter()
+                    .map(|l| l.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                unsupported.join("\n"),
+            )),
+        }
+    }
+
+    /// Orders the libraries of the hdl files in the `file_order` such that every
+    /// library appears after the libraries it depends on.
+    ///
+    /// A library depends on another library when one of its units in the
+    /// `min_order` references a unit from the other library. Ties are broken by
+    /// the library's name. If the libraries depend on each other in a cycle, a
+    /// description of the cycle is also returned and the libraries within the
+    /// cycle are placed last.
+    fn order_libraries(
+        global_graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+        file_order: &Vec<&&IpFileNode>,
+        min_order: &Vec<usize>,
+    ) -> (Vec<Library>, Option<String>) {
+        let names: BTreeSet<String> = file_order
+            .iter()
+            .map(|f| f.get_library().to_string())
+            .collect();
+        let mut graph: GraphMap<String, (), ()> = GraphMap::new();
+        for n in &names {
+            graph.add_node(n.clone(), ());
+        }
+
+        let library_of = |i: usize| {
+            global_graph
+                .get_node_by_index(i)
+                .unwrap()
+                .as_ref()
+                .get_library()
+                .to_string()
+        };
+        // remember the first unit reference that connects two libraries
+        let mut references = HashMap::<(String, String), (usize, usize)>::new();
+        for i in min_order {
+            let lib = library_of(*i);
+            if names.contains(&lib) == false {
+                continue;
+            }
+            for p in global_graph.get_graph().predecessors(*i) {
+                let dep = library_of(p);
+                if dep != lib && names.contains(&dep) == true {
+                    graph.add_edge_by_key(&dep, &lib, ());
+                    references.entry((lib.clone(), dep)).or_insert((*i, p));
+                }
+            }
+        }
+
+        let mut order = graph
+            .get_graph()
+            .topological_sort_by_key(|i| graph.get_key_by_index(i).unwrap().clone());
+
+        // libraries that could not be ordered depend on each other in a cycle
+        let remaining: BTreeSet<usize> = (0..graph.get_map().len())
+            .filter(|i| order.contains(i) == false)
+            .collect();
+        let cycle = match remaining.first() {
+            Some(start) => {
+                // follow the dependencies until a library repeats
+                let mut path = vec![*start];
+                loop {
+                    let next = graph
+                        .get_graph()
+                        .predecessors(*path.last().unwrap())
+                        .filter(|p| remaining.contains(p))
+                        .min_by_key(|p| graph.get_key_by_index(*p).unwrap())
+                        .unwrap();
+                    if let Some(pos) = path.iter().position(|p| *p == next) {
+                        path.drain(0..pos);
+                        path.push(next);
+                        break;
+                    }
+                    path.push(next);
+                }
+                let names: Vec<&String> = path
+                    .iter()
+                    .map(|i| graph.get_key_by_index(*i).unwrap())
+                    .collect();
+                let mut text = format!(
+                    "libraries depend on each other in a cycle: {}",
+                    names
+                        .iter()
+                        .map(|n| n.as_str())
+                        .collect::<Vec<&str>>()
+                        .join(" -> ")
+                );
+                for pair in names.windows(2) {
+                    let (unit, dep) = references.get(&(pair[0].clone(), pair[1].clone())).unwrap();
+                    text.push_str(&format!(
+                        "\n    {} references {}",
+                        global_graph.get_key_by_index(*unit).unwrap(),
+                        global_graph.get_key_by_index(*dep).unwrap()
+                    ));
+                }
+                Some(text)
+            }
+            None => None,
+        };
+        order.extend(remaining);
+
+        let libraries = order
+            .into_iter()
+            .map(|i| {
+                let mut deps: Vec<String> = graph
+                    .get_graph()
+                    .predecessors(i)
+                    .map(|p| graph.get_key_by_index(p).unwrap().clone())
+                    .collect();
+                deps.sort();
+                deps.dedup();
+                Library::new(graph.get_key_by_index(i).unwrap(), deps)
+            })
+            .collect();
+        (libraries, cycle)
+    }
+
+    /// Determines why each hdl file in the `file_order` is written to the blueprint.
+    ///
+    /// The shortest chain of unit references to a file is found by searching the
+    /// `global_graph` from the selected `benches` and then the selected `tops`
+    /// towards their dependencies. Files that cannot be reached from any selected
+    /// unit were only included by `--all`.
+    fn explain_file_order(
+        global_graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+        file_order: &Vec<&&IpFileNode>,
+        min_order: &Vec<usize>,
+        benches: &Vec<usize>,
+        tops: &Vec<usize>,
+    ) -> Explain {
+        // perform a breadth-first search to store each unit's parent and distance from a root
+        let mut visited = HashMap::<usize, (Option<usize>, usize)>::new();
+        // testbenches are searched first so paths begin at the upmost unit
+        for roots in [benches, tops] {
+            let mut queue = VecDeque::<usize>::new();
+            for r in roots {
+                if visited.contains_key(r) == false {
+                    visited.insert(*r, (None, 0));
+                    queue.push_back(*r);
+                }
+            }
+            while let Some(n) = queue.pop_front() {
+                let dist = visited.get(&n).unwrap().1;
+                for p in global_graph.get_graph().predecessors(n) {
+                    if visited.contains_key(&p) == false {
+                        visited.insert(p, (Some(n), dist + 1));
+                        queue.push_back(p);
+                    }
+                }
+            }
+        }
+
+        // trace the path back from the unit to its root
+        let path_to = |i: usize| -> Vec<String> {
+            let mut path = Vec::new();
+            let mut current = Some(i);
+            while let Some(c) = current {
+                path.push(global_graph.get_key_by_index(c).unwrap().to_string());
+                current = visited.get(&c).unwrap().0;
+            }
+            path.reverse();
+            path
+        };
+
+        let mut explain = Explain::new();
+        for ip_file_node in file_order {
+            let file = ip_file_node.get_file();
+            // collect the units associated with this file (primary if the unit is defined in it)
+            let units: Vec<(usize, bool)> = min_order
+                .iter()
+                .filter_map(|i| {
+                    global_graph
+                        .get_node_by_index(*i)
+                        .unwrap()
+                        .as_ref()
+                        .get_associated_files()
+                        .iter()
+                        .position(|f| f.get_file() == file)
+                        .map(|pos| (*i, pos == 0))
+                })
+                .collect();
+            // select the closest referenced unit while preferring units defined in this file
+            let closest = units
+                .iter()
+                .filter(|(i, _)| visited.contains_key(i))
+                .min_by_key(|(i, primary)| (*primary == false, visited.get(i).unwrap().1));
+
+            let (reason, path, merged_with) = match closest {
+                Some((i, true)) => (Reason::Reference, path_to(*i), None),
+                Some((i, false)) => (
+                    Reason::Merge,
+                    path_to(*i),
+                    Some(
+                        global_graph
+                            .get_node_by_index(*i)
+                            .unwrap()
+                            .as_ref()
+                            .get_associated_files()
+                            .first()
+                            .unwrap()
+                            .get_file()
+                            .clone(),
+                    ),
+                ),
+                None => (Reason::All, Vec::new(), None),
+            };
+            explain.add(Explanation::new(
+                file,
+                &ip_file_node.get_library().to_string(),
+                &ip_file_node
+                    .get_ip()
+                    .get_man()
+                    .get_ip()
+                    .into_ip_spec()
+                    .to_string(),
+                reason,
+                units
+                    .iter()
+                    .map(|(i, _)| global_graph.get_key_by_index(*i).unwrap().to_string())
+                    .collect(),
+                path,
+                merged_with,
+            ));
+        }
+        explain
+    }
+
     /// Filters out the local nodes existing within the current IP from the `global_graph`.
     pub fn compute_local_graph<'a>(
         global_graph: &'a GraphMap<CompoundIdentifier, HdlNode, ()>,
@@ -1636,16 +2356,19 @@ impl Plan {
         blueprint: &Blueprint,
         mut envs: Environment,
         target_path: &PathBuf,
+        out_dir: &str,
         top_name: &str,
         top_file: &str,
         top_json: &str,
+        top_jsons: &str,
         bench_name: &str,
         bench_file: &str,
         bench_json: &str,
+        bench_jsons: &str,
         target: &Target,
         require_bench: bool,
     ) -> Result<PathBuf, Fault> {
-        let output_path = target_path.join(target.get_name());
+        let output_path = target_path.join(out_dir);
         // create a output build directorie(s) if they do not exist
         if output_path.exists() == false {
             fs::create_dir_all(&output_path).expect("could not create output directory");
@@ -1686,6 +2409,14 @@ impl Plan {
                     ""
                 },
             ))
+            .add(EnvVar::with(
+                environment::ORBIT_TOPS_JSON,
+                if require_bench == false {
+                    &top_jsons
+                } else {
+                    "[]"
+                },
+            ))
             .add(EnvVar::with(
                 environment::ORBIT_DUT_NAME,
                 if require_bench == true { &top_name } else { "" },
@@ -1698,9 +2429,22 @@ impl Plan {
                 environment::ORBIT_DUT_JSON,
                 if require_bench == true { &top_json } else { "" },
             ))
+            .add(EnvVar::with(
+                environment::ORBIT_DUTS_JSON,
+                if require_bench == true {
+                    &top_jsons
+                } else {
+                    "[]"
+                },
+            ))
+            .add(EnvVar::with(
+                environment::ORBIT_GENERICS,
+                &serde_json::to_string(&generic::to_map(blueprint.get_generics()))?,
+            ))
             .add(EnvVar::with(environment::ORBIT_TB_NAME, &bench_name))
             .add(EnvVar::with(environment::ORBIT_TB_FILE, &bench_file))
             .add(EnvVar::with(environment::ORBIT_TB_JSON, &bench_json))
+            .add(EnvVar::with(environment::ORBIT_TBS_JSON, &bench_jsons))
             .add(EnvVar::with(
                 environment::ORBIT_BLUEPRINT,
                 &blueprint.get_filename(),
@@ -1777,6 +2521,7 @@ impl std::fmt::Display for PlanError {
 #[cfg(test)]
 mod test {
     use super::*;
+    use std::str::FromStr;
 
     #[test]
     fn remove_multi_occur() {
@@ -1797,97 +2542,331 @@ mod test {
             vec![&9, &8, &7, &6, &5, &4]
         );
     }
-}
 
-// impl Plan {
-//     // DEPRECATED: This function may be outdated- was used when `plan` used to be a
-//     // dedicated subcommand.
-
-//     fn execute(self, c: &Context) -> Result<(), Fault> {
-//         // locate the target provided from the command-line
-//         let target = c.select_target(&self.target, self.list == false, true)?;
-
-//         // display targets list and exit
-//         if self.list == true {
-//             match target {
-//                 // display entire contents about the particular plugin
-//                 Some(tg) => println!("{}", tg),
-//                 // display quick overview of all plugins
-//                 None => print!(
-//                     "{}",
-//                     Target::list_targets(
-//                         &mut c
-//                             .get_config()
-//                             .get_targets()
-//                             .values()
-//                             .into_iter()
-//                             .collect::<Vec<&&Target>>(),
-//                         None,
-//                     )
-//                 ),
-//             }
-//             return Ok(());
-//         }
-
-//         // unwrap because at this point the target must exist
-//         let target = target.unwrap();
-
-//         // check that user is in an IP directory
-//         c.jump_to_working_ip()?;
-
-//         // store the working ip struct
-//         let working_ip = Ip::load(c.get_ip_path().unwrap().clone(), true, false)?;
-
-//         // assemble the catalog
-//         let mut catalog = Catalog::new()
-//             .installations(c.get_cache_path())?
-//             .downloads(c.get_downloads_path())?;
-
-//         // @todo: recreate the ip graph from the lockfile, then read each installation
-//         // see Install::install_from_lock_file
-
-//         // this code is only ran if the lock file matches the manifest and we aren't force to recompute
-//         if working_ip.can_use_lock(&catalog) == true && self.force == false {
-//             let le: LockEntry = LockEntry::from((&working_ip, true));
-//             let lf = working_ip.get_lock();
-
-//             let env = Environment::new()
-//                 // read config.toml for setting any env variables
-//                 .from_config(c.get_config())?;
-//             let vtable = StrSwapTable::new().load_environment(&env)?;
-
-//             download_missing_deps(vtable, &lf, &le, &catalog, &c.get_config().get_protocols())?;
-//             // recollect the downloaded items to update the catalog for installations
-//             catalog = catalog.downloads(c.get_downloads_path())?;
-
-//             install_missing_deps(&lf, &le, &catalog)?;
-//             // recollect the installations to update the catalog for dependency graphing
-//             catalog = catalog.installations(c.get_cache_path())?;
-//         }
-
-//         // determine the build directory (command-line arg overrides configuration setting)
-//         let default_target_dir = c.get_target_dir();
-//         let target_dir = match &self.target_dir {
-//             Some(t_dir) => t_dir,
-//             None => &default_target_dir,
-//         };
-
-//         let _ = Self::run(
-//             &working_ip,
-//             target_dir,
-//             target,
-//             catalog,
-//             self.clean,
-//             self.force,
-//             self.only_lock,
-//             self.all,
-//             &self.bench,
-//             &self.top,
-//             &self.filesets,
-//             &Scheme::default(),
-//             false,
-//             true,
-//         );
-//         Ok(())
-//     }
-// }
+    #[test]
+    fn classify_rtl_and_sim() {
+        let ip = Ip::load(PathBuf::from("./tests/s1"), false, false).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let sources = [
+            ("gate.vhd", "entity gate is port (y : out bit); end entity;"),
+            (
+                "top.vhd",
+                "entity top is port (y : out bit); end entity;
+                architecture rtl of top is begin
+                    u0: entity work.gate port map (y => y);
+                end architecture;",
+            ),
+            ("bfm.vhd", "entity bfm is port (y : in bit); end entity;"),
+            (
+                "tb.vhd",
+                "entity tb is end entity;
+                architecture sim of tb is signal y : bit; begin
+                    dut: entity work.top port map (y => y);
+                    u1: entity work.bfm port map (y => y);
+                end architecture;",
+            ),
+        ];
+        let files: Vec<IpFileNode> = sources
+            .iter()
+            .map(|(name, code)| {
+                let path = dir.path().join(name);
+                fs::write(&path, code).unwrap();
+                IpFileNode::new(
+                    path.to_str().unwrap().to_string(),
+                    &ip,
+                    LangIdentifier::new_working(),
+                )
+            })
+            .collect();
+        let graph = Plan::build_full_graph(&files).unwrap();
+        let min_order: Vec<usize> = graph.get_map().values().map(|n| n.index()).collect();
+        let top = graph
+            .get_map()
+            .iter()
+            .find(|(k, _)| k.get_suffix().to_string() == "top")
+            .map(|(_, n)| n.index())
+            .unwrap();
+        let refs: Vec<&IpFileNode> = files.iter().collect();
+        let file_order: Vec<&&IpFileNode> = refs.iter().collect();
+
+        // the testbench's other units are simulation-only once a dut is known
+        assert_eq!(
+            Plan::classify_file_order(&graph, &file_order, &min_order, &vec![top]),
+            vec![Class::Rtl, Class::Rtl, Class::Sim, Class::Sim]
+        );
+        // every unit referenced by the testbench is a possible top-level unit
+        assert_eq!(
+            Plan::classify_file_order(&graph, &file_order, &min_order, &vec![]),
+            vec![Class::Rtl, Class::Rtl, Class::Rtl, Class::Sim]
+        );
+    }
+
+    #[test]
+    fn order_independent_files() {
+        let ip = Ip::load(PathBuf::from("./tests/s1"), false, false).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let files: Vec<IpFileNode> = ["c.vhd", "a.vhd", "b.vhd"]
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(name);
+                let unit = name.trim_end_matches(".vhd");
+                fs::write(&path, format!("entity {} is end entity;", unit)).unwrap();
+                IpFileNode::new(
+                    path.to_str().unwrap().to_string(),
+                    &ip,
+                    LangIdentifier::new_working(),
+                )
+            })
+            .collect();
+        let graph = Plan::build_full_graph(&files).unwrap();
+        // the files are ordered by their keys rather than the order of their units
+        let mut min_order: Vec<usize> = graph.get_map().values().map(|n| n.index()).collect();
+        min_order
+            .sort_by_key(|i| std::cmp::Reverse(graph.get_key_by_index(*i).unwrap().to_string()));
+        let names: Vec<String> = Plan::determine_file_order(&graph, min_order)
+            .iter()
+            .map(|f| {
+                PathBuf::from(f.get_file())
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(names, vec!["a.vhd", "b.vhd", "c.vhd"]);
+    }
+
+    #[test]
+    fn order_libraries() {
+        let dir = tempfile::tempdir().unwrap();
+        // create an ip for each library
+        let ips: Vec<Ip> = ["a", "b", "c"]
+            .iter()
+            .map(|lib| {
+                let root = dir.path().join(lib);
+                fs::create_dir(&root).unwrap();
+                fs::write(
+                    root.join("Orbit.toml"),
+                    format!(
+                        "[ip]\nname = \"{0}\"\nuuid = \"9l9mhzukv5rkdadwrtosianii\"\nversion = \"0.1.0\"\nlibrary = \"{0}\"\n",
+                        lib
+                    ),
+                )
+                .unwrap();
+                Ip::load(root, false, false).unwrap()
+            })
+            .collect();
+        let plan = |sources: &[(usize, &str, &str)]| {
+            let files: Vec<IpFileNode> = sources
+                .iter()
+                .map(|(ip, name, code)| {
+                    let path = ips[*ip].get_root().join(name);
+                    fs::write(&path, code).unwrap();
+                    IpFileNode::new(
+                        path.to_str().unwrap().to_string(),
+                        &ips[*ip],
+                        ips[*ip].get_hdl_library(),
+                    )
+                })
+                .collect();
+            let graph = Plan::build_full_graph(&files).unwrap();
+            let min_order = graph
+                .get_graph()
+                .topological_sort_by_key(|i| Plan::unit_sort_key(&graph, i));
+            let refs: Vec<&IpFileNode> = files.iter().collect();
+            let file_order: Vec<&&IpFileNode> = refs.iter().collect();
+            let (libraries, cycle) = Plan::order_libraries(&graph, &file_order, &min_order);
+            (
+                libraries
+                    .iter()
+                    .map(|l| serde_json::to_string(l).unwrap())
+                    .collect::<Vec<String>>(),
+                cycle,
+            )
+        };
+
+        let (libraries, cycle) = plan(&[
+            (0, "x.vhd", "entity x is end entity;\narchitecture rtl of x is begin u: entity b.y; end architecture;"),
+            (1, "y.vhd", "entity y is end entity;"),
+            (2, "w.vhd", "entity w is end entity;\narchitecture rtl of w is begin u: entity a.x; end architecture;"),
+        ]);
+        assert_eq!(
+            libraries,
+            vec![
+                "{\"name\":\"b\",\"dependencies\":[]}",
+                "{\"name\":\"a\",\"dependencies\":[\"b\"]}",
+                "{\"name\":\"c\",\"dependencies\":[\"a\"]}",
+            ]
+        );
+        assert_eq!(cycle, None);
+
+        // units from two libraries reference each other without a unit cycle
+        let (libraries, cycle) = plan(&[
+            (0, "x.vhd", "entity x is end entity;\narchitecture rtl of x is begin u: entity b.y; end architecture;"),
+            (1, "y.vhd", "entity y is end entity;\narchitecture rtl of y is begin u: entity a.z; end architecture;"),
+            (0, "z.vhd", "entity z is end entity;"),
+        ]);
+        assert_eq!(libraries.len(), 2);
+        assert_eq!(
+            cycle.unwrap(),
+            "libraries depend on each other in a cycle: a -> b -> a\n    a.x references b.y\n    b.y references a.z"
+        );
+    }
+
+    #[test]
+    fn find_every_testbench() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        fs::copy("./tests/s1/Orbit.toml", root.join("Orbit.toml")).unwrap();
+        fs::write(
+            root.join("alu.vhd"),
+            "entity alu is port (a : in bit); end entity;\narchitecture rtl of alu is begin end architecture;",
+        )
+        .unwrap();
+        for tb in ["mul_tb", "alu_tb"] {
+            fs::write(
+                root.join(format!("{}.vhd", tb)),
+                format!("entity {0} is end entity;\narchitecture sim of {0} is\nbegin\nu0: entity work.alu port map (a => '0');\nend architecture;", tb),
+            )
+            .unwrap();
+        }
+        fs::write(root.join("add_tb.v"), "module add_tb;\nendmodule\n").unwrap();
+
+        let ip = Ip::load(root.clone(), true, false).unwrap();
+        let benches = Plan::find_testbenches(&ip, &Catalog::new()).unwrap();
+        assert_eq!(
+            benches,
+            vec![
+                Identifier::from_str("add_tb").unwrap(),
+                Identifier::from_str("alu_tb").unwrap(),
+                Identifier::from_str("mul_tb").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn reproducible_blueprint() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        fs::copy("./tests/s1/Orbit.toml", root.join("Orbit.toml")).unwrap();
+        // independent units give many valid orderings to choose from
+        let mut top =
+            String::from("entity top is end entity;\narchitecture rtl of top is\nbegin\n");
+        for i in [5, 2, 7, 0, 3, 6, 1, 4] {
+            fs::write(
+                root.join(format!("g{}.vhd", i)),
+                format!("entity g{0} is end entity;\narchitecture rtl of g{0} is begin end architecture;", i),
+            )
+            .unwrap();
+            fs::write(root.join(format!("m{}.py", i)), "").unwrap();
+            fs::write(root.join(format!("d{}.txt", i)), "").unwrap();
+            top.push_str(&format!("u{0}: entity work.g{0};\n", i));
+        }
+        top.push_str("end architecture;\n");
+        fs::write(root.join("top.vhd"), top).unwrap();
+
+        let filesets = Some(vec![
+            Fileset::from_str("zeta=m1.py").unwrap(),
+            Fileset::from_str("alpha=d2.txt").unwrap(),
+            Fileset::from_str("mid=g3.vhd").unwrap(),
+        ]);
+
+        const TARGET: &str = r#"
+name = "repro"
+command = "echo"
+fileset.pymdl = "*.py"
+fileset.data = "*.txt"
+fileset.note = "*.md"
+"#;
+
+        // plan the same project repeatedly and compare the blueprints byte for byte
+        let output_path = root.join("target").join("repro");
+        let mut first: Option<Vec<String>> = None;
+        for _ in 0..8 {
+            let ip = Ip::load(root.clone(), true, false).unwrap();
+            // parse the target each time to get a new hash order for its filesets
+            let target = Target::from_str(TARGET).unwrap();
+            let mut blueprints = Vec::new();
+            for scheme in [Scheme::Tsv, Scheme::Json] {
+                let (name, _) = Plan::run(
+                    &ip,
+                    "target",
+                    target.get_name(),
+                    &target,
+                    &Catalog::new(),
+                    true,
+                    false,
+                    false,
+                    true,
+                    false,
+                    &[],
+                    &[],
+                    &filesets,
+                    &[],
+                    &scheme,
+                    false,
+                    false,
+                    Environment::new(),
+                )
+                .unwrap()
+                .unwrap();
+                blueprints.push(fs::read_to_string(output_path.join(name)).unwrap());
+            }
+            match &first {
+                Some(f) => assert_eq!(f, &blueprints),
+                None => first = Some(blueprints),
+            }
+        }
+    }
+
+    #[test]
+    fn join_units() {
+        let units = vec![];
+        assert_eq!(
+            Plan::join_unit_info(&units),
+            (
+                String::new(),
+                String::new(),
+                String::new(),
+                String::from("[]")
+            )
+        );
+
+        let units = vec![(
+            String::from("top"),
+            String::from("/a/top.vhd"),
+            String::from("{}"),
+        )];
+        assert_eq!(
+            Plan::join_unit_info(&units),
+            (
+                String::from("top"),
+                String::from("/a/top.vhd"),
+                String::from("{}"),
+                String::from("[{}]")
+            )
+        );
+
+        let units = vec![
+            (
+                String::from("top"),
+                String::from("/a/top.vhd"),
+                String::from("{\"name\":\"top\"}"),
+            ),
+            (
+                String::from("top2"),
+                String::from("/a/top2.vhd"),
+                String::from("{\"name\":\"top2\"}"),
+            ),
+        ];
+        assert_eq!(
+            Plan::join_unit_info(&units),
+            (
+                String::from("top;top2"),
+                String::from("/a/top.vhd;/a/top2.vhd"),
+                String::from("{\"name\":\"top\"}"),
+                String::from("[{\"name\":\"top\"},{\"name\":\"top2\"}]")
+            )
+        );
+    }
+}
@@ -18,20 +18,31 @@
 use cliproc::{cli, proc, stage::Memory, Arg, Cli, Help, Subcommand};
 
 use crate::commands::helps::test;
+use crate::core::artifacts;
 use crate::core::blueprint::Scheme;
 use crate::core::catalog::Catalog;
 use crate::core::context::Context;
 use crate::core::fileset::Fileset;
+use crate::core::fingerprint::Fingerprint;
+use crate::core::generic::Generic;
 use crate::core::ip::Ip;
+use crate::core::lang::cache::SymbolCache;
 use crate::core::lang::vhdl::token::Identifier;
 use crate::core::swap::StrSwapTable;
-use crate::core::target::Process;
 use crate::core::target::Target;
+use crate::core::watch;
 use crate::error::Error;
 use crate::error::LastError;
-use crate::util::anyerror::Fault;
-use crate::util::environment::ORBIT_OUT_DIR;
+use crate::util::anyerror::{AnyError, Fault};
+use crate::util::environment::{self, ORBIT_OUT_DIR};
 use crate::util::environment::{EnvVar, Environment, ORBIT_TARGET_DIR};
+use crate::util::junit::{TestCase, TestSuite, Verdict};
+use crate::util::logfile::LogFile;
+use crate::util::subprocess;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use super::plan::{self, Plan};
 
@@ -44,12 +55,22 @@ pub struct Test {
     target_dir: Option<String>,
     force: bool,
     all: bool,
+    explain: bool,
+    watch: bool,
     plan: Option<Scheme>,
     verbose: bool,
+    log: bool,
+    quiet: bool,
     dut: Option<Identifier>,
     command: Option<String>,
+    from_step: Option<String>,
+    to_step: Option<String>,
     filesets: Option<Vec<Fileset>>,
-    bench: Option<Identifier>,
+    generics: Option<Vec<Generic>>,
+    bench: Option<Vec<Identifier>>,
+    all_benches: bool,
+    jobs: Option<usize>,
+    patterns: Option<Vec<String>>,
 }
 
 impl Subcommand<Context> for Test {
@@ -59,17 +80,28 @@ impl Subcommand<Context> for Test {
             // Flags
             list: cli.check(Arg::flag("list"))?,
             verbose: cli.check(Arg::flag("verbose"))?,
+            log: cli.check(Arg::flag("log"))?,
+            quiet: cli.check(Arg::flag("quiet"))?,
             force: cli.check(Arg::flag("force"))?,
             all: cli.check(Arg::flag("all"))?,
+            explain: cli.check(Arg::flag("explain"))?,
+            watch: cli.check(Arg::flag("watch"))?,
             dirty: cli.check(Arg::flag("no-clean"))?,
+            all_benches: cli.check(Arg::flag("all-benches"))?,
             // Options
             dut: cli.get(Arg::option("dut").value("unit"))?,
-            bench: cli.get(Arg::option("tb").value("unit"))?,
+            bench: cli.get_all(Arg::option("tb").value("unit"))?,
             plan: cli.get(Arg::option("plan").value("format"))?,
             target: cli.get(Arg::option("target").value("name").switch('t'))?,
             target_dir: cli.get(Arg::option("target-dir"))?,
             command: cli.get(Arg::option("command").value("path"))?,
+            from_step: cli.get(Arg::option("from-step").value("name"))?,
+            to_step: cli.get(Arg::option("to-step").value("name"))?,
+            jobs: cli.get(Arg::option("jobs").switch('j').value("n"))?,
             filesets: cli.get_all(Arg::option("fileset").value("key=glob"))?,
+            generics: cli.get_all(Arg::option("generic").value("name=value"))?,
+            // Positionals
+            patterns: cli.get_all(Arg::positional("glob"))?,
             // Remaining args
             args: cli.remainder()?,
         })
@@ -96,7 +128,7 @@ impl Subcommand<Context> for Test {
                             .values()
                             .into_iter()
                             .collect::<Vec<&&Target>>(),
-                        def_target,
+                        def_target.as_ref(),
                     )
                 ),
             }
@@ -105,14 +137,52 @@ impl Subcommand<Context> for Test {
 
         let target = target.unwrap();
 
+        // verify how the testbenches are selected
+        if self.all_benches == false && self.patterns.is_some() {
+            return Err(Error::TestbenchPatternsWithoutAll)?;
+        }
+        if self.all_benches == true && self.bench.is_some() {
+            return Err(Error::TestbenchWithAllBenches)?;
+        }
+        match self.jobs {
+            Some(0) => return Err(Error::JobsZero)?,
+            Some(_) if self.all_benches == false => return Err(Error::JobsWithoutAllBenches)?,
+            _ => (),
+        }
+
         // coordinate the plan
         let plan = target.coordinate_plan(&self.plan)?;
 
+        // verify the range of steps before planning
+        target.select_steps(&self.from_step, &self.to_step)?;
+
+        // verify the patterns that determine the verdict before planning
+        target.verify_patterns()?;
+
         // check that user is in an IP directory
         c.jump_to_working_ip()?;
 
+        match self.watch {
+            true => {
+                // ignore the outputs that each run writes into the build directory
+                let default_target_dir = c.get_target_dir();
+                let target_dir = self.target_dir.as_ref().unwrap_or(&default_target_dir);
+                let root = c.get_ip_path().unwrap();
+                watch::watch(root, &root.join(target_dir), || {
+                    self.test(c, &target, &plan)
+                })
+            }
+            false => self.test(c, &target, &plan),
+        }
+    }
+}
+
+impl Test {
+    /// Plans and runs the `target` for the selected testbenches of the working
+    /// ip, and then saves the test report.
+    fn test(&self, c: &Context, target: &Target, plan: &Scheme) -> Result<(), Fault> {
         // create the ip manifest
-        let ip = Ip::load(c.get_ip_path().unwrap().clone(), true, false)?;
+        let mut ip = Ip::load(c.get_ip_path().unwrap().clone(), true, false)?;
 
         // @todo: recreate the ip graph from the lockfile, then read each installation
         // see Install::install_from_lock_file
@@ -124,35 +194,389 @@ impl Subcommand<Context> for Test {
             None => &default_build_dir,
         };
 
+        // reuse the symbols parsed for the working ip during previous plans
+        let symbols = SymbolCache::open(&ip.get_root().join(target_dir));
+        ip.set_symbol_cache(symbols);
+
         // gather the catalog and resolve any missing dependencies
         let catalog = Catalog::new()
             .installations(c.get_cache_path())?
             .downloads(c.get_downloads_path())?;
         let catalog = plan::resolve_missing_deps(c, &ip, catalog, self.force)?;
 
-        self.run(
+        if self.all_benches == true {
+            return self.run_all_benches(&ip, target_dir, target, &catalog, c, plan);
+        }
+
+        let mut cases = Vec::new();
+        let result = self.run(
             &ip,
             target_dir,
             target.get_name(),
             target,
-            catalog,
-            &c,
-            &plan,
-        )
+            &catalog,
+            c,
+            plan,
+            self.bench.as_deref().unwrap_or(&[]),
+            &mut cases,
+        );
+        Self::save_report(&ip, target_dir, target, cases)?;
+        result
     }
 }
 
+/// A testbench that is planned and ready for its target to run.
+struct PreparedRun {
+    output_path: PathBuf,
+    target: Target,
+    envs: Environment,
+}
+
 impl Test {
+    /// Runs the target once for every testbench in the working ip that matches
+    /// the glob-style patterns, and then displays a summary of the results.
+    ///
+    /// Each testbench is planned into its own output directory beside the
+    /// target's output directory. When running more than one job, the
+    /// testbenches are planned one at a time and then their targets run
+    /// concurrently.
+    fn run_all_benches(
+        &self,
+        working_ip: &Ip,
+        target_dir: &str,
+        target: &Target,
+        catalog: &Catalog,
+        c: &Context,
+        scheme: &Scheme,
+    ) -> Result<(), Fault> {
+        let patterns = match &self.patterns {
+            Some(p) => p.clone(),
+            None => vec![String::from("*")],
+        };
+        let globs = patterns
+            .iter()
+            .map(|p| match glob::Pattern::new(p) {
+                Ok(g) => Ok(g),
+                Err(e) => Err(AnyError(format!(
+                    "invalid testbench pattern {:?}: {}",
+                    p, e
+                ))),
+            })
+            .collect::<Result<Vec<glob::Pattern>, AnyError>>()?;
+        let match_opts = glob::MatchOptions {
+            case_sensitive: false,
+            require_literal_separator: false,
+            require_literal_leading_dot: false,
+        };
+        let benches: Vec<Identifier> = Plan::find_testbenches(working_ip, catalog)?
+            .into_iter()
+            .filter(|tb| {
+                globs
+                    .iter()
+                    .any(|g| g.matches_with(&tb.to_string(), match_opts))
+            })
+            .collect();
+        if benches.is_empty() == true {
+            return Err(Error::TestbenchesNotFound(patterns.join(", ")))?;
+        }
+
+        let start = Instant::now();
+        let jobs = self.jobs.unwrap_or(1);
+        let cases = match jobs > 1 && benches.len() > 1 {
+            true => self.run_concurrently(
+                working_ip, target_dir, target, catalog, c, scheme, &benches, jobs,
+            )?,
+            false => {
+                let mut cases = Vec::new();
+                for (i, bench) in benches.iter().enumerate() {
+                    crate::info!(
+                        "testing {} ({} of {})",
+                        bench.to_string().blue(),
+                        i + 1,
+                        benches.len()
+                    );
+                    if let Err(e) = self.run(
+                        working_ip,
+                        target_dir,
+                        &target.get_bench_out_dir(&bench.to_string()),
+                        target,
+                        catalog,
+                        c,
+                        scheme,
+                        &[bench.clone()],
+                        &mut cases,
+                    ) {
+                        crate::warn!("testbench {} failed: {}", bench, e);
+                    }
+                    // an interrupt stops the remaining testbenches from running
+                    if subprocess::is_interrupted() == true {
+                        break;
+                    }
+                }
+                cases
+            }
+        };
+
+        println!("{}", Self::format_summary(&cases, start.elapsed()));
+        let failures = cases.iter().filter(|tc| tc.is_pass() == false).count();
+        let total = cases.len();
+        Self::save_report(working_ip, target_dir, target, cases)?;
+        if subprocess::is_interrupted() == true {
+            return Err(Error::ChildProcInterrupted)?;
+        }
+        match failures {
+            0 => Ok(()),
+            n => Err(Error::TestbenchesFailed(n, total))?,
+        }
+    }
+
+    /// Plans every testbench and then runs up to `jobs` of their targets at the
+    /// same time.
+    ///
+    /// The output of each target is recorded in a log and displayed together
+    /// once the target finishes, so the outputs of different testbenches do not
+    /// interleave.
+    fn run_concurrently(
+        &self,
+        working_ip: &Ip,
+        target_dir: &str,
+        target: &Target,
+        catalog: &Catalog,
+        c: &Context,
+        scheme: &Scheme,
+        benches: &[Identifier],
+        jobs: usize,
+    ) -> Result<Vec<TestCase>, Fault> {
+        // the test cases are kept in the same order as the testbenches
+        let mut cases: Vec<Vec<TestCase>> = benches.iter().map(|_| Vec::new()).collect();
+        // planning reads from shared state, so it happens one testbench at a time
+        let mut prepared: Vec<Option<PreparedRun>> = Vec::with_capacity(benches.len());
+        for (i, bench) in benches.iter().enumerate() {
+            let start = Instant::now();
+            match self.prepare(
+                working_ip,
+                target_dir,
+                &target.get_bench_out_dir(&bench.to_string()),
+                target,
+                catalog,
+                c,
+                scheme,
+                &[bench.clone()],
+            ) {
+                Ok(run) => prepared.push(Some(run)),
+                Err(e) => {
+                    crate::warn!("testbench {} failed: {}", bench, e);
+                    cases[i].extend(self.to_test_cases(
+                        None,
+                        &[bench.clone()],
+                        start.elapsed(),
+                        Verdict::Error(e.to_string()),
+                        None,
+                    ));
+                    prepared.push(None);
+                }
+            }
+        }
+
+        crate::info!(
+            "executing target {} for {} testbenches with {} jobs",
+            target.get_name().green(),
+            benches.len(),
+            jobs
+        );
+        let next = AtomicUsize::new(0);
+        let outcomes: Mutex<Vec<Option<(Result<(), String>, Option<String>, Duration)>>> =
+            Mutex::new(benches.iter().map(|_| None).collect());
+        std::thread::scope(|scope| {
+            for _ in 0..jobs.min(benches.len()) {
+                scope.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+                    // an interrupt stops the remaining testbenches from running
+                    if i >= benches.len() || subprocess::is_interrupted() == true {
+                        break;
+                    }
+                    let run = match &prepared[i] {
+                        Some(run) => run,
+                        None => continue,
+                    };
+                    let start = Instant::now();
+                    let log = match run.target.output_check() {
+                        Ok(check) => LogFile::create(&run.output_path, run.target.get_name(), true)
+                            .map(|log| log.checking(check)),
+                        Err(e) => Err(e.into()),
+                    };
+                    let (result, output) = match log {
+                        Ok(log) => (
+                            self.launch(run, Some(&log)).map_err(|e| e.to_string()),
+                            Some(log),
+                        ),
+                        Err(e) => (Err(e.to_string()), None),
+                    };
+                    let elapsed = start.elapsed();
+                    // display the entire output of a testbench at once
+                    let mut outcomes = outcomes.lock().unwrap();
+                    crate::info!(
+                        "testbench {} finished in {:.2}s",
+                        benches[i].to_string().blue(),
+                        elapsed.as_secs_f64()
+                    );
+                    if let Some(text) = output.as_ref().and_then(|l| l.read_output().ok()) {
+                        print!("{}", text);
+                    }
+                    if let Err(e) = &result {
+                        crate::warn!("testbench {} failed: {}", benches[i], e);
+                    }
+                    outcomes[i] = Some((result, output.map(|l| l.excerpt()), elapsed));
+                });
+            }
+        });
+
+        let outcomes = outcomes.into_inner().unwrap();
+        for (i, ((bench, run), outcome)) in benches.iter().zip(prepared).zip(outcomes).enumerate() {
+            if let (Some(run), Some((result, output, elapsed))) = (run, outcome) {
+                let verdict = self.conclude(working_ip, &run, result);
+                if let Verdict::Error(e) = &verdict {
+                    crate::warn!("testbench {} failed: {}", bench, e);
+                }
+                cases[i].extend(self.to_test_cases(
+                    Some(&run),
+                    &[bench.clone()],
+                    elapsed,
+                    verdict,
+                    output.as_deref(),
+                ));
+            }
+        }
+        Ok(cases.into_iter().flatten().collect())
+    }
+
+    /// Creates a table displaying the verdict and duration of each testbench.
+    fn format_summary(cases: &[TestCase], total: Duration) -> String {
+        let mut table = format!(
+            "{:<32}{:<10}{}\n{3:->32}{3:->10}{3:->10}\n",
+            "Testbench", "Result", "Duration", " "
+        );
+        for case in cases {
+            table.push_str(&format!(
+                "{:<32}{:<10}{:.2}s\n",
+                case.get_name(),
+                match case.get_verdict() {
+                    Verdict::Pass => "pass",
+                    Verdict::Fail(_) => "fail",
+                    Verdict::Error(_) => "error",
+                },
+                case.get_time().as_secs_f64()
+            ));
+        }
+        let passed = cases.iter().filter(|tc| tc.is_pass() == true).count();
+        table.push_str(&format!(
+            "\n{} passed, {} failed in {:.2}s",
+            passed,
+            cases.len() - passed,
+            total.as_secs_f64()
+        ));
+        table
+    }
+
+    /// Writes the JUnit XML report of the `cases` to the target's output
+    /// directory.
+    fn save_report(
+        working_ip: &Ip,
+        target_dir: &str,
+        target: &Target,
+        cases: Vec<TestCase>,
+    ) -> Result<(), Fault> {
+        if cases.is_empty() == true {
+            return Ok(());
+        }
+        let suite = TestSuite::new(
+            &working_ip.get_man().get_ip().get_name().to_string(),
+            target.get_name(),
+            cases,
+        );
+        let path = suite.save(
+            &working_ip
+                .get_root()
+                .join(target_dir)
+                .join(target.get_name()),
+        )?;
+        crate::info!("test report saved at: {:?}", path);
+        Ok(())
+    }
+
+    /// Plans the testbenches and then runs the target, recording the result of
+    /// each testbench in `cases`.
     fn run(
         &self,
         working_ip: &Ip,
         target_dir: &str,
         out_dir: &str,
         target: &Target,
-        catalog: Catalog,
+        catalog: &Catalog,
         c: &Context,
         scheme: &Scheme,
+        benches: &[Identifier],
+        cases: &mut Vec<TestCase>,
     ) -> Result<(), Fault> {
+        let start = Instant::now();
+        let run = match self.prepare(
+            working_ip, target_dir, out_dir, target, catalog, c, scheme, benches,
+        ) {
+            Ok(run) => run,
+            Err(e) => {
+                cases.extend(self.to_test_cases(
+                    None,
+                    benches,
+                    start.elapsed(),
+                    Verdict::Error(e.to_string()),
+                    None,
+                ));
+                return Err(e);
+            }
+        };
+
+        // run the command from the output path
+        crate::info!("executing target {}", run.target.get_name().green());
+        // the output is only captured when it is saved or checked against patterns
+        let check = run.target.output_check()?;
+        let log = match (self.log || self.quiet, check.is_some()) {
+            (true, _) => Some(
+                LogFile::create(&run.output_path, run.target.get_name(), self.quiet)?
+                    .checking(check),
+            ),
+            (false, true) => Some(LogFile::memory().checking(check)),
+            (false, false) => None,
+        };
+        let result = self.launch(&run, log.as_ref());
+        if let Some(log) = &log {
+            log.report(result.is_err())?;
+        }
+        let verdict = self.conclude(working_ip, &run, result.map_err(|e| e.to_string()));
+        cases.extend(self.to_test_cases(
+            Some(&run),
+            benches,
+            start.elapsed(),
+            verdict.clone(),
+            log.map(|l| l.excerpt()).as_deref(),
+        ));
+        match verdict {
+            Verdict::Pass => Ok(()),
+            Verdict::Fail(e) | Verdict::Error(e) => Err(AnyError(e))?,
+        }
+    }
+
+    /// Plans the `benches` into the output directory `out_dir`.
+    fn prepare(
+        &self,
+        working_ip: &Ip,
+        target_dir: &str,
+        out_dir: &str,
+        target: &Target,
+        catalog: &Catalog,
+        c: &Context,
+        scheme: &Scheme,
+        benches: &[Identifier],
+    ) -> Result<PreparedRun, Fault> {
         let envs = Environment::new()
             // read config.toml for setting any env variables
             .from_config(c.get_config())?
@@ -165,15 +589,18 @@ impl Test {
         Plan::run(
             &working_ip,
             target_dir,
+            out_dir,
             target,
             catalog,
-            self.dirty == false,
+            self.dirty == false && self.from_step.is_none(),
             self.force,
             false,
             self.all,
-            &self.bench,
-            &self.dut,
+            self.explain,
+            benches,
+            self.dut.as_slice(),
             &self.filesets,
+            self.generics.as_deref().unwrap_or(&[]),
             &scheme,
             true,
             true,
@@ -188,17 +615,228 @@ impl Test {
         let swap_table = StrSwapTable::new().load_environment(&envs)?;
         let target = target.clone().replace_vars_in_args(&swap_table);
 
-        // run the command from the output path
-        crate::info!("executing target {}", target.get_name().green());
-        match target.execute(
+        // the outputs will no longer reflect the last recorded build
+        Fingerprint::remove(&output_path)?;
+
+        Ok(PreparedRun {
+            output_path: output_path,
+            target: target,
+            envs: envs,
+        })
+    }
+
+    /// Checks if the entire target is being ran rather than only some of its
+    /// steps.
+    fn is_whole_run(&self) -> bool {
+        self.from_step.is_none() && self.to_step.is_none()
+    }
+
+    /// Runs the target of a planned testbench, capturing its output in the `log`
+    /// when given.
+    ///
+    /// When running the entire target, the output must also pass the log's
+    /// check of the target's patterns.
+    fn launch(&self, run: &PreparedRun, log: Option<&LogFile>) -> Result<(), Fault> {
+        if let Err(e) = run.target.run(
             &self.command,
             &self.args,
             self.verbose,
-            &output_path,
-            envs.into_map(),
+            &run.output_path,
+            run.envs.into_map(),
+            &self.from_step,
+            &self.to_step,
+            log,
         ) {
-            Ok(()) => Ok(()),
-            Err(e) => Err(Error::TargetProcFailed(LastError(e.to_string())))?,
+            return Err(Error::TargetProcFailed(LastError(e.to_string())))?;
+        }
+        match (self.is_whole_run(), log) {
+            (true, Some(log)) => Ok(log.verdict()?),
+            _ => Ok(()),
+        }
+    }
+
+    /// Determines the verdict from the `result` of launching a planned
+    /// testbench, collecting the target's artifacts when it passes.
+    fn conclude(&self, working_ip: &Ip, run: &PreparedRun, result: Result<(), String>) -> Verdict {
+        match result {
+            Ok(()) => match self.is_whole_run() {
+                // keep the artifacts from running the entire target
+                true => match artifacts::collect_target_artifacts(
+                    &run.target,
+                    &run.output_path,
+                    working_ip,
+                ) {
+                    Ok(()) => Verdict::Pass,
+                    Err(e) => Verdict::Error(e.to_string()),
+                },
+                false => Verdict::Pass,
+            },
+            Err(e) => Verdict::Fail(e),
         }
     }
+
+    /// Creates a test case for each testbench that was planned.
+    ///
+    /// The names of the testbench and device under test are read from the
+    /// planned environment, falling back to the requested `benches` when the
+    /// planning did not finish.
+    fn to_test_cases(
+        &self,
+        run: Option<&PreparedRun>,
+        benches: &[Identifier],
+        time: Duration,
+        verdict: Verdict,
+        stdout: Option<&str>,
+    ) -> Vec<TestCase> {
+        let read = |key: &str| {
+            run.and_then(|r| r.envs.get(key))
+                .map(|v| v.get_value().to_string())
+                .unwrap_or_default()
+        };
+        let names: Vec<String> = match read(environment::ORBIT_TB_NAME) {
+            s if s.is_empty() == false => s
+                .split(environment::LIST_SEPARATOR)
+                .map(|n| n.to_string())
+                .collect(),
+            _ => benches.iter().map(|b| b.to_string()).collect(),
+        };
+        let dut = match read(environment::ORBIT_DUT_NAME) {
+            s if s.is_empty() == false => s,
+            _ => self.dut.as_ref().map(|d| d.to_string()).unwrap_or_default(),
+        };
+        names
+            .iter()
+            .map(|name| {
+                TestCase::new(
+                    name,
+                    match dut.is_empty() {
+                        true => name,
+                        false => &dut,
+                    },
+                    time,
+                    verdict.clone(),
+                    stdout,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    /// Creates the command to run every testbench with up to `jobs` at once.
+    fn all_benches(jobs: usize) -> Test {
+        Test {
+            target: None,
+            args: Vec::new(),
+            list: false,
+            dirty: false,
+            target_dir: None,
+            force: false,
+            all: false,
+            explain: false,
+            watch: false,
+            plan: None,
+            verbose: false,
+            log: false,
+            quiet: false,
+            dut: None,
+            command: None,
+            from_step: None,
+            to_step: None,
+            filesets: None,
+            generics: None,
+            bench: None,
+            all_benches: true,
+            jobs: Some(jobs),
+            patterns: None,
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn run_benches_concurrently() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        std::fs::copy("./tests/s1/Orbit.toml", root.join("Orbit.toml")).unwrap();
+        for tb in ["a_tb", "b_tb", "c_tb"] {
+            std::fs::write(
+                root.join(format!("{}.vhd", tb)),
+                format!(
+                    "entity {0} is end entity;\narchitecture sim of {0} is begin end architecture;",
+                    tb
+                ),
+            )
+            .unwrap();
+        }
+        // the first testbench finishes last and the second one fails
+        let mut target = Target::from_str(
+            r#"
+name = "check"
+command = "sh"
+args = ["-c", "case $ORBIT_TB_NAME in c_tb) sleep 0.3;; b_tb) exit 1;; esac"]
+"#,
+        )
+        .unwrap();
+        target.set_root(root.clone());
+        let ip = Ip::load(root.clone(), true, false).unwrap();
+        let (catalog, c) = (Catalog::new(), Context::new());
+        let benches: Vec<Identifier> = ["c_tb", "missing_tb", "a_tb", "b_tb"]
+            .iter()
+            .map(|tb| Identifier::from_str(tb).unwrap())
+            .collect();
+
+        let cases = all_benches(2)
+            .run_concurrently(
+                &ip,
+                "target",
+                &target,
+                &catalog,
+                &c,
+                &Scheme::Tsv,
+                &benches,
+                2,
+            )
+            .unwrap();
+        assert_eq!(
+            cases
+                .iter()
+                .map(|tc| format!(
+                    "{} {}",
+                    tc.get_name(),
+                    match tc.get_verdict() {
+                        Verdict::Pass => "pass",
+                        Verdict::Fail(_) => "fail",
+                        Verdict::Error(_) => "error",
+                    }
+                ))
+                .collect::<Vec<String>>(),
+            vec!["c_tb pass", "missing_tb error", "a_tb pass", "b_tb fail"]
+        );
+
+        // the results of every testbench are combined into a single report
+        let result =
+            all_benches(2).run_all_benches(&ip, "target", &target, &catalog, &c, &Scheme::Tsv);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            Error::TestbenchesFailed(1, 3).to_string()
+        );
+        let report =
+            std::fs::read_to_string(root.join("target").join("check").join("junit.xml")).unwrap();
+        let names: Vec<usize> = ["a_tb", "b_tb", "c_tb"]
+            .iter()
+            .map(|tb| report.find(&format!("name=\"{}\"", tb)).unwrap())
+            .collect();
+        assert_eq!(names.windows(2).all(|w| w[0] < w[1]), true);
+        assert_eq!(
+            root.join("target")
+                .join("check-a_tb")
+                .join("blueprint.tsv")
+                .exists(),
+            true
+        );
+    }
 }
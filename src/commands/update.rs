@@ -0,0 +1,476 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use super::plan::{self, Plan};
+use crate::commands::helps::update;
+use crate::core::algo;
+use crate::core::catalog::{Catalog, PkgName};
+use crate::core::context::Context;
+use crate::core::ip::Ip;
+use crate::core::lockfile::{LockEntry, LockFile, IP_LOCK_FILE};
+use crate::core::manifest::{Dependency, FromFile, IpName};
+use crate::core::swap::StrSwapTable;
+use crate::core::uuid::Uuid;
+use crate::core::version::{AnyVersion, Version};
+use crate::error::{Error, Hint};
+use crate::util::anyerror::Fault;
+use crate::util::environment::Environment;
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Debug, PartialEq)]
+pub struct Update {
+    ips: Vec<IpName>,
+}
+
+impl Subcommand<Context> for Update {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(update::HELP))?;
+        // collect all positional arguments
+        let mut ips: Vec<IpName> = Vec::new();
+        while let Some(ip) = cli.get(Arg::positional("ip"))? {
+            ips.push(ip);
+        }
+        let command = Ok(Update { ips: ips });
+        command
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // check that user is in an IP directory
+        c.jump_to_working_ip()?;
+
+        // store the working ip struct
+        let working_ip = Ip::load(c.get_ip_path().unwrap().clone(), true, false)?;
+
+        // assemble the catalog with the ip available from the channels
+        let mut catalog = Catalog::new()
+            .installations(c.get_cache_path())?
+            .downloads(c.get_downloads_path())?
+            .available(&c.get_config().get_channels())?;
+
+        let before = working_ip.get_lock().clone();
+
+        // plan the lockfile entries to fetch before computing the new ip graph
+        let (lf, updated) = {
+            let resolved = Self::resolve(&working_ip, &catalog, &self.ips)?;
+            let updated: HashSet<Uuid> = resolved.iter().map(|ip| ip.get_uuid().clone()).collect();
+            // dependencies that are not being updated keep their locked versions
+            let mut entries: Vec<LockEntry> = before
+                .inner()
+                .iter()
+                .filter(|e| {
+                    e.is_relative() == false
+                        && e.get_uuid() != working_ip.get_uuid()
+                        && updated.contains(e.get_uuid()) == false
+                })
+                .cloned()
+                .collect();
+            entries.extend(resolved.into_iter().map(|ip| LockEntry::from((ip, false))));
+            (LockFile::wrap(entries), updated)
+        };
+
+        let le: LockEntry = LockEntry::from((&working_ip, true));
+
+        let env = Environment::new()
+            // read config.toml for setting any env variables
+            .from_config(c.get_config())?;
+        let vtable = StrSwapTable::new().load_environment(&env)?;
+
+        plan::download_missing_deps(vtable, &lf, &le, &catalog, &c.get_config().get_protocols())?;
+        // recollect the downloaded items to update the catalog for installations
+        catalog = catalog.downloads(c.get_downloads_path())?;
+
+        plan::install_missing_deps(&lf, &le, &catalog)?;
+        // recollect the installations to update the catalog for dependency graphing
+        catalog = catalog.installations(c.get_cache_path())?;
+
+        // hide any other installed versions of the dependencies that are not being updated
+        for entry in lf.inner() {
+            if updated.contains(entry.get_uuid()) == true {
+                continue;
+            }
+            let versions: Vec<&Version> = lf
+                .inner()
+                .iter()
+                .filter(|e| e.get_uuid() == entry.get_uuid())
+                .map(|e| e.get_version())
+                .collect();
+            if let Some(lvl) = catalog.inner_mut().get_mut(entry.get_uuid()) {
+                lvl.keep_installs(&versions);
+            }
+        }
+
+        // build entire ip graph and resolve with dynamic symbol transformation
+        let ip_graph = match algo::compute_final_ip_graph(&working_ip, &catalog) {
+            Ok(g) => g,
+            Err(e) => return Err(e)?,
+        };
+        Plan::write_lockfile(&working_ip, &ip_graph, true, true, &catalog)?;
+
+        let after = LockFile::from_file(&working_ip.get_root().join(IP_LOCK_FILE))?;
+        Self::report(&before, &after, working_ip.get_uuid());
+        Ok(())
+    }
+}
+
+impl Update {
+    /// Finds the newest versions in the `catalog` that satisfy the manifest for
+    /// each dependency listed in `names`, along with the ip they depend on that
+    /// must change.
+    ///
+    /// A dependency of a selected ip keeps its locked version when it still
+    /// satisfies the requirement. All dependencies of the `working_ip` and its
+    /// relative dependencies are selected when `names` is empty, which updates
+    /// every ip to its newest version.
+    ///
+    /// Only the ip whose versions are changing are returned.
+    fn resolve<'a>(
+        working_ip: &Ip,
+        catalog: &'a Catalog,
+        names: &[IpName],
+    ) -> Result<Vec<&'a Ip>, Fault> {
+        let mut found = HashSet::new();
+        let mut processing: Vec<(&Ip, bool)> = Vec::new();
+
+        // search the local ips for the direct dependencies to update
+        let mut locals = vec![(working_ip, true)];
+        let mut visited_locals = HashSet::new();
+        while let Some((ip, is_working)) = locals.pop() {
+            if visited_locals.insert(ip.get_root().clone()) == false {
+                continue;
+            }
+            for (name, dep) in ip.get_man().get_deps_list(is_working, true) {
+                if dep.is_relative() == true {
+                    if let Some(relative_ip) = dep.as_ip() {
+                        locals.push((relative_ip, false));
+                    }
+                    continue;
+                }
+                if names.is_empty() == false && names.contains(name) == false {
+                    continue;
+                }
+                found.insert(name);
+                processing.push((Self::find_newest(working_ip, catalog, name, dep)?, true));
+            }
+        }
+        if let Some(missing) = names.iter().find(|n| found.contains(n) == false) {
+            return Err(Error::DependencyNotFound(missing.clone()))?;
+        }
+
+        // follow each selected dependency to its own dependencies
+        let mut resolved = Vec::new();
+        let mut visited = HashSet::new();
+        while let Some((ip, is_changed)) = processing.pop() {
+            if visited.insert(ip.get_man().get_ip().into_ip_spec()) == false {
+                continue;
+            }
+            for (name, dep) in ip.get_man().get_deps_list(false, true) {
+                if dep.is_relative() == true {
+                    continue;
+                }
+                let locked = match names.is_empty() {
+                    true => None,
+                    false => Self::find_locked(working_ip, catalog, name, dep),
+                };
+                processing.push(match locked {
+                    Some(ip) => (ip, false),
+                    None => (Self::find_newest(working_ip, catalog, name, dep)?, true),
+                });
+            }
+            if is_changed == true {
+                resolved.push(ip);
+            }
+        }
+        Ok(resolved)
+    }
+
+    /// Returns the version of the dependency `name` recorded in the
+    /// `working_ip`'s lockfile if it still satisfies the requirement of `dep`
+    /// and is known to the `catalog`.
+    fn find_locked<'a>(
+        working_ip: &Ip,
+        catalog: &'a Catalog,
+        name: &IpName,
+        dep: &Dependency,
+    ) -> Option<&'a Ip> {
+        let entry = working_ip.get_lock().inner().iter().find(|e| {
+            e.is_relative() == false
+                && e.get_name() == name
+                && dep.as_uuid().map(|id| id == e.get_uuid()).unwrap_or(true) == true
+                && dep.get_version().matches(e.get_version()) == true
+        })?;
+        catalog
+            .inner()
+            .get(entry.get_uuid())?
+            .get(true, true, &AnyVersion::from(entry.get_version()))
+            .filter(|ip| ip.get_man().get_ip().get_version() == entry.get_version())
+    }
+
+    /// Returns the highest version of the dependency `name` known to the
+    /// `catalog` that satisfies the requirement of `dep`.
+    fn find_newest<'a>(
+        working_ip: &Ip,
+        catalog: &'a Catalog,
+        name: &IpName,
+        dep: &Dependency,
+    ) -> Result<&'a Ip, Fault> {
+        // try to pull the uuid from the lockfile if the manifest is missing the uuid
+        let uuid = match dep.as_uuid() {
+            Some(id) => Some(id),
+            None => working_ip
+                .get_lock()
+                .get(name, dep.get_version())
+                .map(|e| e.get_uuid()),
+        };
+        let lvl = match catalog.translate_name(&PkgName::new(name, uuid))? {
+            Some(lvl) => lvl,
+            None => {
+                return Err(Error::IpNotFoundAnywhere(
+                    name.to_string(),
+                    Hint::CatalogList,
+                ))?
+            }
+        };
+        match lvl.get(true, true, &AnyVersion::from(dep.get_version())) {
+            Some(ip) => Ok(ip),
+            None => Err(Error::DependencyVersionNotFound(
+                name.clone(),
+                dep.get_version().clone(),
+                Hint::ShowVersions,
+            ))?,
+        }
+    }
+
+    /// Displays the versions of each dependency in the lockfile `before` and
+    /// `after` the update.
+    fn report(before: &LockFile, after: &LockFile, root: &Uuid) {
+        let rows = Self::compare(before, after, root);
+        if rows.is_empty() == true {
+            crate::info!("ip has no dependencies to update");
+            return;
+        }
+        print!("{}", Self::format_table(&rows));
+        match rows.iter().filter(|(_, old, new)| old != new).count() {
+            0 => crate::info!("dependencies are already up to date"),
+            n => crate::info!("updated {} of {} dependencies", n, rows.len()),
+        }
+    }
+
+    /// Lists the versions of each dependency in the lockfile `before` and
+    /// `after` the update, ordered by name.
+    ///
+    /// A dependency missing from either lockfile has its versions listed as `-`.
+    fn compare<'a>(
+        before: &'a LockFile,
+        after: &'a LockFile,
+        root: &Uuid,
+    ) -> Vec<(&'a IpName, String, String)> {
+        let mut rows: BTreeMap<(&IpName, &Uuid), (Vec<String>, Vec<String>)> = BTreeMap::new();
+        for (i, lf) in [before, after].iter().enumerate() {
+            for entry in lf.inner() {
+                if entry.get_uuid() == root || entry.is_relative() == true {
+                    continue;
+                }
+                let row = rows
+                    .entry((entry.get_name(), entry.get_uuid()))
+                    .or_default();
+                match i {
+                    0 => row.0.push(entry.get_version().to_string()),
+                    _ => row.1.push(entry.get_version().to_string()),
+                }
+            }
+        }
+        let versions = |list: &Vec<String>| match list.is_empty() {
+            true => String::from("-"),
+            false => list.join(", "),
+        };
+        rows.into_iter()
+            .map(|((name, _), (old, new))| (name, versions(&old), versions(&new)))
+            .collect()
+    }
+
+    /// Creates a table displaying the versions of each dependency before and
+    /// after the update.
+    fn format_table(rows: &[(&IpName, String, String)]) -> String {
+        let mut table = format!(
+            "{:<24}{:<16}{}\n{3:->24}{3:->16}{3:->10}\n",
+            "Ip", "Before", "After", " "
+        );
+        for (name, old, new) in rows {
+            table.push_str(&format!("{:<24}{:<16}{}\n", name.to_string(), old, new));
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::{Path, PathBuf};
+    use std::str::FromStr;
+
+    const GATES: &str = "0hpy4bp4ywykitvwnoxlojt03";
+    const ADDERS: &str = "a1b2c3d4e5f6g7h8i9j0k1l2m";
+
+    /// Writes the manifest of an ip into its own directory within `dir`.
+    fn write_ip(dir: &Path, name: &str, uuid: &str, version: &str, deps: &str) -> PathBuf {
+        let root = dir.join(format!("{}-{}", name, version));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(
+            root.join("Orbit.toml"),
+            format!(
+                "[ip]\nname = \"{}\"\nuuid = \"{}\"\nversion = \"{}\"\n\n[dependencies]\n{}",
+                name, uuid, version, deps
+            ),
+        )
+        .unwrap();
+        root
+    }
+
+    /// Creates a lockfile that locks each ip in `entries` to a version.
+    fn write_lock(root: &Path, entries: &[(&str, &str, &str)]) -> LockFile {
+        let mut text = String::from("version = 1\n\n");
+        for (name, uuid, version) in entries {
+            text.push_str(&format!(
+                "[[ip]]\nname = \"{}\"\nversion = \"{}\"\nuuid = \"{}\"\nchecksum = \"{}\"\ndependencies = []\n\n",
+                name,
+                version,
+                uuid,
+                "0".repeat(64)
+            ));
+        }
+        std::fs::write(root.join(IP_LOCK_FILE), &text).unwrap();
+        LockFile::from_str(&text).unwrap()
+    }
+
+    /// Lists the name and version of each resolved ip in order.
+    fn specs(ips: Vec<&Ip>) -> Vec<String> {
+        let mut list: Vec<String> = ips
+            .iter()
+            .map(|ip| {
+                let ip = ip.get_man().get_ip();
+                format!("{}@{}", ip.get_name(), ip.get_version())
+            })
+            .collect();
+        list.sort();
+        list
+    }
+
+    #[test]
+    fn keep_locked_dependencies() {
+        let cache = tempfile::tempdir().unwrap();
+        for version in ["1.0.0", "1.5.0", "1.9.0"] {
+            write_ip(cache.path(), "gates", GATES, version, "");
+        }
+        write_ip(
+            cache.path(),
+            "adders",
+            ADDERS,
+            "0.1.0",
+            "gates = \"^1.0\"\n",
+        );
+        write_ip(
+            cache.path(),
+            "adders",
+            ADDERS,
+            "0.2.0",
+            "gates = \"^1.5\"\n",
+        );
+        let cache_path = cache.path().to_path_buf();
+        let catalog = Catalog::new().installations(&cache_path).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = write_ip(
+            dir.path(),
+            "app",
+            "exoh08z9p1fbj8pah3vlcljpd",
+            "0.1.0",
+            "adders = \">=0.1\"\n",
+        );
+        let adders = vec![IpName::from_str("adders").unwrap()];
+
+        // the locked version of a dependency is kept while it satisfies the requirement
+        write_lock(
+            &root,
+            &[("adders", ADDERS, "0.1.0"), ("gates", GATES, "1.5.0")],
+        );
+        let app = Ip::load(root.clone(), true, false).unwrap();
+        assert_eq!(
+            specs(Update::resolve(&app, &catalog, &adders).unwrap()),
+            vec!["adders@0.2.0"]
+        );
+        // every dependency is updated when none are named
+        assert_eq!(
+            specs(Update::resolve(&app, &catalog, &[]).unwrap()),
+            vec!["adders@0.2.0", "gates@1.9.0"]
+        );
+
+        // the locked version of a dependency is updated once it no longer satisfies the requirement
+        write_lock(
+            &root,
+            &[("adders", ADDERS, "0.1.0"), ("gates", GATES, "1.0.0")],
+        );
+        let app = Ip::load(root.clone(), true, false).unwrap();
+        assert_eq!(
+            specs(Update::resolve(&app, &catalog, &adders).unwrap()),
+            vec!["adders@0.2.0", "gates@1.9.0"]
+        );
+
+        // only direct dependencies can be named
+        assert_eq!(
+            Update::resolve(&app, &catalog, &[IpName::from_str("gates").unwrap()]).is_err(),
+            true
+        );
+    }
+
+    #[test]
+    fn compare_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let root_uuid = Uuid::from_str("exoh08z9p1fbj8pah3vlcljpd").unwrap();
+        let before = write_lock(
+            &root,
+            &[
+                ("gates", GATES, "1.5.0"),
+                ("adders", ADDERS, "0.1.0"),
+                ("app", "exoh08z9p1fbj8pah3vlcljpd", "0.1.0"),
+            ],
+        );
+        let after = write_lock(
+            &root,
+            &[
+                ("gates", GATES, "1.5.0"),
+                ("adders", ADDERS, "0.2.0"),
+                ("app", "exoh08z9p1fbj8pah3vlcljpd", "0.1.0"),
+                ("muxes", "9l9mhzukv5rkdadwrtosianii", "1.0.0"),
+            ],
+        );
+        let rows = Update::compare(&before, &after, &root_uuid);
+        assert_eq!(
+            rows.iter()
+                .map(|(n, old, new)| format!("{} {} {}", n, old, new))
+                .collect::<Vec<String>>(),
+            vec!["adders 0.1.0 0.2.0", "gates 1.5.0 1.5.0", "muxes - 1.0.0"]
+        );
+        assert_eq!(
+            Update::format_table(&rows).lines().nth(2).unwrap(),
+            "adders                  0.1.0           0.2.0"
+        );
+        assert_eq!(Update::compare(&before, &before, &root_uuid).len(), 2);
+    }
+}
@@ -15,6 +15,7 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
@@ -171,9 +172,7 @@ fn graph_ip<'a>(
                     match catalog.translate_name(&PkgName::new(pkgid, uuid))? {
                         Some(status) => {
                             // find this IP to read its dependencies
-                            match status.get_install(&AnyVersion::Specific(
-                                dependency.get_version().clone(),
-                            )) {
+                            match status.get_install(&AnyVersion::from(dependency.get_version())) {
                                 Some(cached_ip) => {
                                     // check if node is already in graph ????
                                     let s = if let Some(existing_node) = g.get_node_by_key(
@@ -345,8 +344,15 @@ pub fn build_ip_file_list<'a>(
     working_ip: &Ip,
 ) -> Vec<IpFileNode<'a>> {
     let mut files = Vec::new();
-    ip_graph.get_map().iter().for_each(|(_, ip)| {
-        let inner_ip = ip.as_ref().as_ip();
+    // visit the ips in a stable order to keep the file list reproducible
+    let mut ips: Vec<(&IpSpec, &IpNode)> = ip_graph
+        .get_map()
+        .iter()
+        .map(|(spec, ip)| (spec, ip.as_ref()))
+        .collect();
+    ips.sort_by(|a, b| a.0.partial_cmp(b.0).unwrap_or(Ordering::Equal));
+    ips.into_iter().for_each(|(_, ip)| {
+        let inner_ip = ip.as_ip();
         let non_private_list = inner_ip.into_non_private_list();
         inner_ip
             .gather_current_files()
@@ -360,11 +366,7 @@ pub fn build_ip_file_list<'a>(
                 (fileset::is_vhdl(f)) || (fileset::is_verilog(f)) || (fileset::is_systemverilog(f))
             })
             .for_each(|f| {
-                files.push(IpFileNode::new(
-                    f,
-                    inner_ip,
-                    ip.as_ref().get_library().clone(),
-                ));
+                files.push(IpFileNode::new(f, inner_ip, ip.get_library().clone()));
             })
     });
     files
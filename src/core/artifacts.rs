@@ -0,0 +1,264 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! The files a target declares as its artifacts.
+//!
+//! After a successful run, the artifacts are copied out of the target's output
+//! directory into a separate directory for the working ip's version. This
+//! directory is kept when the output directory is cleaned.
+
+use crate::core::ip::Ip;
+use crate::core::target::Target;
+use crate::util::anyerror::{AnyError, Fault};
+use crate::util::filesystem;
+use crate::util::logfile::LOGS_DIR;
+use crate::util::sha256;
+use ignore::WalkBuilder;
+use serde_derive::Serialize;
+use std::path::PathBuf;
+
+/// The directory within the target's output directory that stores the collected
+/// artifacts.
+pub const ARTIFACTS_DIR: &str = "artifacts";
+
+/// The file that lists the collected artifacts for a single version.
+pub const ARTIFACTS_INDEX_FILE: &str = ".orbit-artifacts";
+
+/// A single collected file.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Artifact {
+    path: String,
+    size: u64,
+    sha256: String,
+}
+
+/// The list of artifacts collected for a version of the working ip.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ArtifactIndex {
+    target: String,
+    ip: String,
+    version: String,
+    files: Vec<Artifact>,
+}
+
+impl ArtifactIndex {
+    /// Copies the files in the `output_path` that match any of the glob-style
+    /// `patterns` into the artifacts directory for the `version` of the `ip`.
+    ///
+    /// Any artifacts previously collected for the same version are replaced.
+    /// Returns the directory storing the artifacts along with the index of
+    /// the files that were collected.
+    pub fn collect(
+        output_path: &PathBuf,
+        patterns: &[String],
+        target: &str,
+        ip: &str,
+        version: &str,
+    ) -> Result<(PathBuf, Self), Fault> {
+        let match_opts = glob::MatchOptions {
+            case_sensitive: false,
+            require_literal_separator: false,
+            require_literal_leading_dot: false,
+        };
+        let globs = patterns
+            .iter()
+            .map(|p| match glob::Pattern::new(p) {
+                Ok(g) => Ok(g),
+                Err(e) => Err(AnyError(format!("invalid artifact pattern {:?}: {}", p, e))),
+            })
+            .collect::<Result<Vec<glob::Pattern>, AnyError>>()?;
+
+        let dest = output_path.join(ARTIFACTS_DIR).join(version);
+        if dest.exists() == true {
+            std::fs::remove_dir_all(&dest)?;
+        }
+
+        // previously collected artifacts and logs are never collected again
+        let (artifacts_root, logs_root) =
+            (output_path.join(ARTIFACTS_DIR), output_path.join(LOGS_DIR));
+        let mut files: Vec<PathBuf> = WalkBuilder::new(output_path)
+            .standard_filters(false)
+            .filter_entry(move |p| p.path() != artifacts_root && p.path() != logs_root)
+            .build()
+            .filter_map(|result| match result {
+                Ok(entry) => match entry.path().is_file() {
+                    true => Some(entry.into_path()),
+                    false => None,
+                },
+                Err(_) => None,
+            })
+            .collect();
+        files.sort();
+
+        let mut index = Self {
+            target: target.to_string(),
+            ip: ip.to_string(),
+            version: version.to_string(),
+            files: Vec::new(),
+        };
+        for file in files {
+            let rel_path = filesystem::into_std_str(filesystem::remove_base(output_path, &file));
+            if globs
+                .iter()
+                .any(|g| g.matches_with(&rel_path, match_opts) == true)
+                == false
+            {
+                continue;
+            }
+            let bytes = std::fs::read(&file)?;
+            let to = dest.join(&rel_path);
+            std::fs::create_dir_all(to.parent().unwrap())?;
+            std::fs::write(&to, &bytes)?;
+            index.files.push(Artifact {
+                path: rel_path,
+                size: bytes.len() as u64,
+                sha256: sha256::compute_sha256(&bytes).to_string(),
+            });
+        }
+        if index.files.is_empty() == false {
+            let mut data = serde_json::to_string_pretty(&index)?;
+            data.push('\n');
+            std::fs::write(dest.join(ARTIFACTS_INDEX_FILE), data)?;
+        }
+        Ok((dest, index))
+    }
+
+    /// Returns the number of collected files.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+}
+
+/// Collects the artifacts declared by the `target` from the `output_path` after
+/// a successful run for the working `ip`.
+pub fn collect_target_artifacts(
+    target: &Target,
+    output_path: &PathBuf,
+    ip: &Ip,
+) -> Result<(), Fault> {
+    let patterns = match target.get_artifacts() {
+        Some(p) => p,
+        None => return Ok(()),
+    };
+    let (dest, index) = ArtifactIndex::collect(
+        output_path,
+        patterns,
+        target.get_name(),
+        &ip.get_man().get_ip().get_name().to_string(),
+        &ip.get_man().get_ip().get_version().to_string(),
+    )?;
+    match index.len() {
+        0 => crate::warn!(
+            "target {} produced no files matching its artifacts",
+            target.get_name()
+        ),
+        n => crate::info!("collected {} artifact(s) at: {:?}", n, dest),
+    }
+    Ok(())
+}
+
+/// Removes everything within the `output_path` except for the collected
+/// artifacts, the logs of previous runs, and the files named in `keep`.
+pub fn clean_output(output_path: &PathBuf, keep: &[&str]) -> Result<(), Fault> {
+    for entry in std::fs::read_dir(output_path)? {
+        let path = entry?.path();
+        let name = path.file_name().unwrap();
+        if (name == ARTIFACTS_DIR || name == LOGS_DIR) && path.is_dir() == true {
+            continue;
+        }
+        if keep.iter().any(|k| name == *k) == true && path.is_file() == true {
+            continue;
+        }
+        match path.is_dir() {
+            true => std::fs::remove_dir_all(&path)?,
+            false => std::fs::remove_file(&path)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn collect_and_keep_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().to_path_buf();
+        std::fs::create_dir_all(out.join("reports")).unwrap();
+        std::fs::write(out.join("top.bit"), "bits").unwrap();
+        std::fs::write(out.join("reports").join("timing.rpt"), "slack").unwrap();
+        std::fs::write(out.join("blueprint.tsv"), "").unwrap();
+
+        let patterns = vec![String::from("*.bit"), String::from("reports/*.rpt")];
+        let (dest, index) =
+            ArtifactIndex::collect(&out, &patterns, "vivado", "alu", "1.0.0").unwrap();
+        assert_eq!(dest, out.join(ARTIFACTS_DIR).join("1.0.0"));
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.files[0].path, "reports/timing.rpt");
+        assert_eq!(index.files[1].path, "top.bit");
+        assert_eq!(
+            index.files[1].sha256,
+            sha256::compute_sha256("bits".as_bytes()).to_string()
+        );
+        assert_eq!(dest.join("reports").join("timing.rpt").exists(), true);
+        assert_eq!(dest.join(ARTIFACTS_INDEX_FILE).exists(), true);
+
+        // previously collected artifacts are not collected again
+        let (_, index) = ArtifactIndex::collect(&out, &patterns, "vivado", "alu", "1.0.1").unwrap();
+        assert_eq!(index.len(), 2);
+
+        // cleaning keeps the collected artifacts and the requested files
+        clean_output(&out, &["blueprint.tsv", "reports"]).unwrap();
+        assert_eq!(out.join("top.bit").exists(), false);
+        assert_eq!(out.join("reports").exists(), false);
+        assert_eq!(out.join("blueprint.tsv").exists(), true);
+        assert_eq!(dest.join("top.bit").exists(), true);
+        assert_eq!(
+            out.join(ARTIFACTS_DIR)
+                .join("1.0.1")
+                .join("top.bit")
+                .exists(),
+            true
+        );
+    }
+
+    #[test]
+    fn separate_bench_outputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let (out, bench_out) = (dir.path().join("sim"), dir.path().join("sim-alu_tb"));
+        for path in [&out, &bench_out] {
+            std::fs::create_dir_all(path.join(LOGS_DIR)).unwrap();
+            std::fs::write(path.join("wave.log"), "wave").unwrap();
+            std::fs::write(path.join(LOGS_DIR).join("sim.log"), "log").unwrap();
+        }
+        let patterns = vec![String::from("*.log")];
+        let (dest, index) =
+            ArtifactIndex::collect(&bench_out, &patterns, "sim", "alu", "1.0.0").unwrap();
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.files[0].path, "wave.log");
+
+        // running the target without a testbench leaves the testbench's outputs
+        let (_, index) = ArtifactIndex::collect(&out, &patterns, "sim", "alu", "1.0.0").unwrap();
+        assert_eq!(index.len(), 1);
+        clean_output(&out, &[]).unwrap();
+        assert_eq!(out.join("wave.log").exists(), false);
+        assert_eq!(bench_out.join("wave.log").exists(), true);
+        assert_eq!(bench_out.join(LOGS_DIR).join("sim.log").exists(), true);
+        assert_eq!(dest.join("wave.log").exists(), true);
+    }
+}
@@ -16,19 +16,27 @@
 //
 
 use crate::core::fileset;
+use crate::core::generic::{self, Generic};
 use crate::util::anyerror::AnyError;
 use cliproc::cli::Error;
 use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::io::Write;
 use std::{fs::File, path::PathBuf, str::FromStr};
 
 use super::algo::IpFileNode;
+use super::ip::Ip;
+use super::lang::Lang;
+use super::manifest::FileAttributes;
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub enum Scheme {
+    #[serde(rename = "tsv", alias = "Tsv")]
     Tsv,
-    // Json,
+    #[serde(rename = "json")]
+    Json,
 }
 
 impl Default for Scheme {
@@ -44,6 +52,7 @@ impl Display for Scheme {
             "{}",
             match self {
                 Self::Tsv => "tsv",
+                Self::Json => "json",
             }
         )
     }
@@ -55,53 +64,198 @@ impl FromStr for Scheme {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_ascii_lowercase().as_ref() {
             "tsv" => Ok(Self::Tsv),
-            // "json" => Ok(Self::Json),
+            "json" => Ok(Self::Json),
             _ => Err(AnyError(format!("unknown file format: {}", s))),
         }
     }
 }
 
+/// The name of the fileset that lists the generics in a tsv blueprint.
+pub const GENERIC_FILESET: &str = "GENERIC";
+
+/// The classification of a hdl file in the blueprint.
+#[derive(Debug, PartialEq, Serialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Class {
+    /// The file is required by a design unit outside of any testbench.
+    Rtl,
+    /// The file is only required by testbenches.
+    Sim,
+}
+
+impl Display for Class {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Rtl => "rtl",
+                Self::Sim => "sim",
+            }
+        )
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Instruction<'a, 'b> {
-    Hdl(&'b IpFileNode<'a>),
-    Auxiliary(String, String, String),
+    Hdl(&'b IpFileNode<'a>, Class),
+    Auxiliary(String, String, String, &'a Ip),
 }
 
 impl<'a, 'b> Instruction<'a, 'b> {
     pub fn write(&self, format: &Scheme) -> String {
         match &format {
             Scheme::Tsv => match &self {
-                Self::Hdl(node) => {
-                    // match on what type of file we have
-                    let source_set = if fileset::is_verilog(node.get_file()) == true {
-                        "VLOG"
-                    } else if fileset::is_vhdl(node.get_file()) == true {
-                        "VHDL"
-                    } else if fileset::is_systemverilog(node.get_file()) == true {
-                        "SYSV"
-                    } else {
-                        panic!("unknown file in source file set")
-                    };
-                    format!(
-                        "{}\t{}\t{}",
-                        source_set,
-                        node.get_library(),
-                        node.get_file()
-                    )
-                }
-                Self::Auxiliary(key, lib, file) => format!("{}\t{}\t{}", key, lib, file),
+                Self::Hdl(node, _) => format!(
+                    "{}\t{}\t{}",
+                    Self::get_source_set(node),
+                    node.get_library(),
+                    node.get_file()
+                ),
+                Self::Auxiliary(key, lib, file, _) => format!("{}\t{}\t{}", key, lib, file),
             },
-            // Scheme::Json => {
-            //     todo!()
-            // }
+            Scheme::Json => serde_json::to_string(&self.as_step()).unwrap(),
+        }
+    }
+
+    /// References the file path written for this instruction.
+    pub fn get_path(&self) -> &String {
+        match &self {
+            Self::Hdl(node, _) => node.get_file(),
+            Self::Auxiliary(_, _, file, _) => file,
+        }
+    }
+
+    /// Determines the built-in fileset for the hdl file `node`.
+    fn get_source_set(node: &IpFileNode) -> &'static str {
+        // match on what type of file we have
+        if fileset::is_verilog(node.get_file()) == true {
+            "VLOG"
+        } else if fileset::is_vhdl(node.get_file()) == true {
+            "VHDL"
+        } else if fileset::is_systemverilog(node.get_file()) == true {
+            "SYSV"
+        } else {
+            panic!("unknown file in source file set")
+        }
+    }
+
+    /// Collects the information about this instruction into a structured step.
+    fn as_step(&self) -> Step {
+        match &self {
+            Self::Hdl(node, class) => Step {
+                fileset: Self::get_source_set(node).to_string(),
+                library: node.get_library().to_string(),
+                path: node.get_file().clone(),
+                language: Some(node.get_language().clone()),
+                class: Some(*class),
+                attributes: node.get_ip().get_file_attributes(node.get_file()),
+                ip: StepIp::from(node.get_ip()),
+            },
+            Self::Auxiliary(key, lib, file, ip) => Step {
+                fileset: key.clone(),
+                library: lib.clone(),
+                path: file.clone(),
+                language: None,
+                class: None,
+                attributes: ip.get_file_attributes(file),
+                ip: StepIp::from(*ip),
+            },
+        }
+    }
+}
+
+/// A single entry of a structured blueprint.
+#[derive(Debug, PartialEq, Serialize)]
+struct Step {
+    fileset: String,
+    library: String,
+    path: String,
+    language: Option<Lang>,
+    class: Option<Class>,
+    attributes: FileAttributes,
+    ip: StepIp,
+}
+
+/// The identifying information of the ip that owns a file in the blueprint.
+#[derive(Debug, PartialEq, Serialize)]
+struct StepIp {
+    name: String,
+    version: String,
+    uuid: String,
+}
+
+impl From<&Ip> for StepIp {
+    fn from(value: &Ip) -> Self {
+        Self {
+            name: value.get_man().get_ip().get_name().to_string(),
+            version: value.get_man().get_ip().get_version().to_string(),
+            uuid: value.get_uuid().to_string(),
+        }
+    }
+}
+
+/// A design unit selected during planning (such as the top or testbench).
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Unit {
+    name: String,
+    file: String,
+    interface: Value,
+}
+
+impl Unit {
+    /// Creates a new unit from its `name`, the `file` it is defined in, and the
+    /// serialized `json` of its interface.
+    ///
+    /// Returns `None` if the `name` is empty (no unit was selected).
+    pub fn new(name: &str, file: &str, json: &str) -> Option<Self> {
+        match name.is_empty() {
+            true => None,
+            false => Some(Self {
+                name: name.to_string(),
+                file: file.to_string(),
+                interface: serde_json::from_str(json).unwrap_or(Value::Null),
+            }),
+        }
+    }
+}
+
+/// A hdl library along with the other libraries its units reference.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Library {
+    name: String,
+    dependencies: Vec<String>,
+}
+
+impl Library {
+    pub fn new(name: &str, dependencies: Vec<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            dependencies: dependencies,
         }
     }
 }
 
+/// The layout of a blueprint written with the json scheme.
+#[derive(Debug, PartialEq, Serialize)]
+struct JsonBlueprint<'c> {
+    top: &'c Vec<Unit>,
+    dut: &'c Vec<Unit>,
+    tb: &'c Vec<Unit>,
+    libraries: &'c Vec<Library>,
+    generics: BTreeMap<&'c str, &'c str>,
+    steps: Vec<Step>,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Blueprint<'a, 'b> {
     scheme: Scheme,
     steps: Vec<Instruction<'a, 'b>>,
+    top: Vec<Unit>,
+    dut: Vec<Unit>,
+    tb: Vec<Unit>,
+    libraries: Vec<Library>,
+    generics: Vec<Generic>,
 }
 
 impl<'a, 'b> Default for Blueprint<'a, 'b> {
@@ -109,6 +263,11 @@ impl<'a, 'b> Default for Blueprint<'a, 'b> {
         Self {
             scheme: Scheme::default(),
             steps: Vec::default(),
+            top: Vec::new(),
+            dut: Vec::new(),
+            tb: Vec::new(),
+            libraries: Vec::new(),
+            generics: Vec::new(),
         }
     }
 }
@@ -117,14 +276,14 @@ impl<'a, 'b> Blueprint<'a, 'b> {
     pub fn new(scheme: Scheme) -> Self {
         Self {
             scheme: scheme,
-            steps: Vec::new(),
+            ..Default::default()
         }
     }
 
     pub fn get_filename(&self) -> String {
         String::from(match self.scheme {
             Scheme::Tsv => "blueprint.tsv",
-            // Scheme::Json => "blueprint.json",
+            Scheme::Json => "blueprint.json",
         })
     }
 
@@ -133,17 +292,131 @@ impl<'a, 'b> Blueprint<'a, 'b> {
         self.steps.push(instr);
     }
 
+    /// Collects the file paths of every instruction in the blueprint in order.
+    pub fn get_files(&self) -> Vec<String> {
+        self.steps.iter().map(|i| i.get_path().clone()).collect()
+    }
+
+    /// Sets the selected units for the top-level, design-under-test, and testbench.
+    ///
+    /// Multiple units may be selected for each role. Only schemes that support
+    /// structured data will write this information.
+    pub fn set_units(&mut self, top: Vec<Unit>, dut: Vec<Unit>, tb: Vec<Unit>) {
+        self.top = top;
+        self.dut = dut;
+        self.tb = tb;
+    }
+
+    /// Sets the hdl libraries in the order they should be compiled.
+    ///
+    /// Only schemes that support structured data will write this information.
+    pub fn set_libraries(&mut self, libraries: Vec<Library>) {
+        self.libraries = libraries;
+    }
+
+    /// Sets the generics to override on the top-level unit.
+    ///
+    /// The tsv scheme writes each generic as a line in the `GENERIC` fileset,
+    /// where the library column is the generic's name and the path column is
+    /// its value.
+    pub fn set_generics(&mut self, generics: Vec<Generic>) {
+        self.generics = generics;
+    }
+
+    pub fn get_generics(&self) -> &Vec<Generic> {
+        &self.generics
+    }
+
     pub fn write(&self, output_path: &PathBuf) -> Result<(PathBuf, usize), Error> {
         let blueprint_path = output_path.join(self.get_filename());
         let mut fd = File::create(&blueprint_path).expect("could not create blueprint file");
         // write the data
-        let data = self.steps.iter().fold(String::new(), |mut acc, i| {
-            acc.push_str(i.write(&self.scheme).as_ref());
-            acc.push('\n');
-            acc
-        });
+        let data = match &self.scheme {
+            Scheme::Tsv => {
+                let generics = generic::to_map(&self.generics).into_iter().fold(
+                    String::new(),
+                    |mut acc, (k, v)| {
+                        acc.push_str(&format!("{}\t{}\t{}\n", GENERIC_FILESET, k, v));
+                        acc
+                    },
+                );
+                self.steps.iter().fold(generics, |mut acc, i| {
+                    acc.push_str(i.write(&self.scheme).as_ref());
+                    acc.push('\n');
+                    acc
+                })
+            }
+            Scheme::Json => {
+                let doc = JsonBlueprint {
+                    top: &self.top,
+                    dut: &self.dut,
+                    tb: &self.tb,
+                    libraries: &self.libraries,
+                    generics: generic::to_map(&self.generics),
+                    steps: self.steps.iter().map(|i| i.as_step()).collect(),
+                };
+                let mut data = serde_json::to_string_pretty(&doc).unwrap();
+                data.push('\n');
+                data
+            }
+        };
         fd.write_all(data.as_bytes())
             .expect("failed to write data to blueprint");
         Ok((blueprint_path, self.steps.len()))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scheme_from_str() {
+        assert_eq!(Scheme::from_str("tsv").unwrap(), Scheme::Tsv);
+        assert_eq!(Scheme::from_str("JSON").unwrap(), Scheme::Json);
+        assert_eq!(Scheme::from_str("csv").is_err(), true);
+    }
+
+    #[test]
+    fn write_json_step() {
+        let ip = Ip::load(PathBuf::from("./tests/s1"), false, false).unwrap();
+        let instr = Instruction::Auxiliary(
+            String::from("PYMDL"),
+            String::from("test"),
+            String::from("/a/b/model.py"),
+            &ip,
+        );
+        assert_eq!(
+            instr.write(&Scheme::Json),
+            "{\"fileset\":\"PYMDL\",\"library\":\"test\",\"path\":\"/a/b/model.py\",\"language\":null,\"class\":null,\"attributes\":{},\"ip\":{\"name\":\"test\",\"version\":\"0.1.0\",\"uuid\":\"9l9mhzukv5rkdadwrtosianii\"}}"
+        );
+        assert_eq!(instr.write(&Scheme::Tsv), "PYMDL\ttest\t/a/b/model.py");
+    }
+
+    #[test]
+    fn write_generics() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().to_path_buf();
+        let generics = vec![
+            Generic::from_str("WIDTH=16").unwrap(),
+            Generic::from_str("DEPTH=4").unwrap(),
+        ];
+
+        let mut blueprint = Blueprint::new(Scheme::Tsv);
+        blueprint.set_generics(generics.clone());
+        let (path, _) = blueprint.write(&out).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "GENERIC\tDEPTH\t4\nGENERIC\tWIDTH\t16\n"
+        );
+
+        let mut blueprint = Blueprint::new(Scheme::Json);
+        blueprint.set_generics(generics);
+        let (path, _) = blueprint.write(&out).unwrap();
+        let doc: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(
+            doc["generics"],
+            serde_json::json!({"DEPTH": "4", "WIDTH": "16"})
+        );
+    }
+}
@@ -161,6 +161,12 @@ impl IpLevel {
         &self.available
     }
 
+    /// Removes every installation whose version is not one of the `versions`.
+    pub fn keep_installs(&mut self, versions: &[&Version]) -> () {
+        self.installs
+            .retain(|ip| versions.contains(&ip.get_man().get_ip().get_version()));
+    }
+
     pub fn is_available(&self) -> bool {
         self.available.is_empty() == false
     }
@@ -265,12 +271,7 @@ impl IpLevel {
         let mut latest_version: Option<&Ip> = None;
         space
             .iter()
-            .filter(|ip| match &target {
-                AnyVersion::Specific(v) => {
-                    crate::core::version::is_compatible(v, ip.get_man().get_ip().get_version())
-                }
-                AnyVersion::Latest => true,
-            })
+            .filter(|ip| target.matches(ip.get_man().get_ip().get_version()))
             .for_each(|ip| {
                 if latest_version.is_none()
                     || ip.get_man().get_ip().get_version()
@@ -571,6 +572,67 @@ mod test {
         let ce = CacheEntry::from(&Uuid::nil());
         assert_eq!("0000000000000000000000000000", ce.offset());
     }
+
+    #[test]
+    fn keep_only_installs() {
+        let dir = tempfile::tempdir().unwrap();
+        for version in ["1.0.0", "1.5.0", "1.9.0"] {
+            let root = dir.path().join(format!("gates-{}", version));
+            std::fs::create_dir_all(&root).unwrap();
+            std::fs::write(
+                root.join("Orbit.toml"),
+                format!(
+                    "[ip]\nname = \"gates\"\nuuid = \"0hpy4bp4ywykitvwnoxlojt03\"\nversion = \"{}\"\n",
+                    version
+                ),
+            )
+            .unwrap();
+        }
+        let path = dir.path().to_path_buf();
+        let mut catalog = Catalog::new().installations(&path).unwrap();
+        let uuid = Uuid::from_str("0hpy4bp4ywykitvwnoxlojt03").unwrap();
+        let installs = |catalog: &Catalog| -> Vec<String> {
+            let mut list: Vec<String> = catalog
+                .inner()
+                .get(&uuid)
+                .unwrap()
+                .get_installations()
+                .iter()
+                .map(|ip| ip.get_man().get_ip().get_version().to_string())
+                .collect();
+            list.sort();
+            list
+        };
+        assert_eq!(installs(&catalog), vec!["1.0.0", "1.5.0", "1.9.0"]);
+
+        let (v1, v2) = (
+            Version::from_str("1.0.0").unwrap(),
+            Version::from_str("1.9.0").unwrap(),
+        );
+        let lvl = catalog.inner_mut().get_mut(&uuid).unwrap();
+        lvl.keep_installs(&[&v1, &v2]);
+        assert_eq!(installs(&catalog), vec!["1.0.0", "1.9.0"]);
+        // the newest remaining install satisfies a requirement for any version
+        assert_eq!(
+            catalog
+                .inner()
+                .get(&uuid)
+                .unwrap()
+                .get_install(&AnyVersion::Latest)
+                .unwrap()
+                .get_man()
+                .get_ip()
+                .get_version(),
+            &v2
+        );
+
+        catalog
+            .inner_mut()
+            .get_mut(&uuid)
+            .unwrap()
+            .keep_installs(&[]);
+        assert_eq!(installs(&catalog).is_empty(), true);
+    }
 }
 
 type Checksum = String;
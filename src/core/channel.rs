@@ -32,6 +32,8 @@ pub type Channels = Vec<Channel>;
 pub struct Sequence {
     command: String,
     args: Option<Vec<String>>,
+    timeout: Option<u64>,
+    retries: Option<u32>,
     #[serde(skip_deserializing, skip_serializing)]
     root: PathBuf,
 }
@@ -48,8 +50,16 @@ impl Process for Sequence {
         }
     }
 
-    fn get_command(&self) -> &String {
-        &self.command
+    fn get_command(&self) -> Result<&String, Error> {
+        Ok(&self.command)
+    }
+
+    fn get_timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+
+    fn get_retries(&self) -> u32 {
+        self.retries.unwrap_or(0)
     }
 }
 
@@ -125,21 +135,45 @@ impl Channel {
 
     pub fn run_sync(&self, env: &Environment) -> Result<(), Fault> {
         match &self.sync {
-            Some(cmd) => cmd.execute(&None, &Vec::new(), false, self.get_root(), env.into_map()),
+            Some(cmd) => cmd.execute(
+                &None,
+                &Vec::new(),
+                false,
+                self.get_root(),
+                env.into_map(),
+                false,
+                None,
+            ),
             None => Ok(()),
         }
     }
 
     pub fn run_pre(&self, env: &Environment) -> Result<(), Fault> {
         match &self.pre {
-            Some(cmd) => cmd.execute(&None, &Vec::new(), false, self.get_root(), env.into_map()),
+            Some(cmd) => cmd.execute(
+                &None,
+                &Vec::new(),
+                false,
+                self.get_root(),
+                env.into_map(),
+                false,
+                None,
+            ),
             None => Ok(()),
         }
     }
 
     pub fn run_post(&self, env: &Environment) -> Result<(), Fault> {
         match &self.post {
-            Some(cmd) => cmd.execute(&None, &Vec::new(), false, self.get_root(), env.into_map()),
+            Some(cmd) => cmd.execute(
+                &None,
+                &Vec::new(),
+                false,
+                self.get_root(),
+                env.into_map(),
+                false,
+                None,
+            ),
             None => Ok(()),
         }
     }
@@ -21,6 +21,7 @@ use crate::core::protocol::Protocol;
 use crate::core::protocol::Protocols;
 use crate::core::target::{Target, Targets};
 use crate::error::Error;
+use crate::error::Hint;
 use crate::error::LastError;
 use crate::util::anyerror::AnyError;
 use crate::util::filesystem;
@@ -708,6 +709,74 @@ impl Config {
         map
     }
 
+    /// Resolves the target `name` into its full definition by applying the
+    /// targets it extends.
+    ///
+    /// Returns `None` if no target is named `name`.
+    pub fn get_target(&self, name: &str) -> Result<Option<Target>, Error> {
+        let targets = match &self.target {
+            Some(t) => t,
+            None => return Ok(None),
+        };
+        let index = match targets.iter().position(|t| t.get_name() == name) {
+            Some(i) => i,
+            None => return Ok(None),
+        };
+        let target = Self::resolve_target(targets, index, &mut Vec::new())?;
+        target.validate()?;
+        Ok(Some(target))
+    }
+
+    /// Applies the chain of targets extended by the target at `index`.
+    ///
+    /// The `targets` are in precedence order, so a target extends the first
+    /// target with the matching name. A target that extends its own name
+    /// extends the next definition of that name found in a configuration with
+    /// lower precedence.
+    fn resolve_target(
+        targets: &[Target],
+        index: usize,
+        chain: &mut Vec<usize>,
+    ) -> Result<Target, Error> {
+        // detect inheritance cycles
+        if let Some(start) = chain.iter().position(|&i| i == index) {
+            let names: Vec<&str> = chain[start..]
+                .iter()
+                .chain(std::iter::once(&index))
+                .map(|&i| targets[i].get_name())
+                .collect();
+            return Err(Error::TargetExtendsCycle(names.join(" -> ")));
+        }
+        let target = &targets[index];
+        let parent_name = match target.get_extends() {
+            Some(p) => p,
+            None => return Ok(target.clone()),
+        };
+        let skip = match parent_name == target.get_name() {
+            true => index + 1,
+            false => 0,
+        };
+        let parent_index = match targets
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .find(|(_, t)| t.get_name() == parent_name)
+        {
+            Some((i, _)) => i,
+            None => {
+                return Err(Error::TargetExtendsNotFound(
+                    target.get_name().to_string(),
+                    parent_name.to_string(),
+                    Hint::TargetsList,
+                ))
+            }
+        };
+        chain.push(index);
+        let parent = Self::resolve_target(targets, parent_index, chain)?;
+        chain.pop();
+        Ok(target.clone().extend(&parent))
+    }
+
     pub fn get_channels(&self) -> HashMap<&String, &Channel> {
         let mut map = HashMap::new();
 
@@ -783,6 +852,8 @@ impl FromFile for Config {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::core::blueprint::Scheme;
+    use crate::core::target::Process;
 
     const C_0: &str = r#"
 # This is a blank configuration file.
@@ -816,6 +887,49 @@ args = ["./download.py"]
 
 [vhdl-format]
 tab-size = 3
+"#;
+
+    const C_2: &str = r#"
+[[target]]
+name = "sim"
+extends = "sim"
+args = ["--std=08"]
+
+[[target]]
+name = "sim-gui"
+extends = "sim"
+args = ["--gui"]
+fileset.waves = "*.wcfg"
+
+[[target]]
+name = "elab"
+extends = "sim"
+command = "ghdl"
+args = ["-e"]
+
+[[target]]
+name = "a"
+extends = "b"
+
+[[target]]
+name = "b"
+extends = "a"
+
+[[target]]
+name = "lost"
+extends = "missing"
+
+[[target]]
+name = "empty"
+"#;
+
+    const C_3: &str = r#"
+[[target]]
+name = "sim"
+command = "python"
+args = ["sim.py"]
+plans = ["json"]
+fileset.do-file = "*.do"
 "#;
 
     #[test]
@@ -840,6 +954,51 @@ tab-size = 3
         }
     }
 
+    #[test]
+    fn resolve_extended_targets() {
+        let mut cfg = Config::from_str(C_2).unwrap();
+        cfg.append(Config::from_str(C_3).unwrap());
+
+        // the arguments and filesets are combined when the command is inherited
+        let gui = cfg.get_target("sim-gui").unwrap().unwrap();
+        assert_eq!(gui.get_command().unwrap(), "python");
+        assert_eq!(gui.get_args(), vec!["sim.py", "--std=08", "--gui"]);
+        assert_eq!(gui.get_extends(), None);
+        assert_eq!(
+            gui.get_filesets().unwrap().keys().collect::<Vec<&String>>(),
+            vec!["do-file", "waves"]
+        );
+        assert_eq!(gui.coordinate_plan(&None).unwrap(), Scheme::Json);
+
+        // a new command starts with only its own arguments
+        let elab = cfg.get_target("elab").unwrap().unwrap();
+        assert_eq!(elab.get_command().unwrap(), "ghdl");
+        assert_eq!(elab.get_args(), vec!["-e"]);
+
+        // extending its own name inherits from the lower precedence layer
+        let sim = cfg.get_target("sim").unwrap().unwrap();
+        assert_eq!(sim.get_command().unwrap(), "python");
+        assert_eq!(sim.get_args(), vec!["sim.py", "--std=08"]);
+
+        assert_eq!(
+            cfg.get_target("a"),
+            Err(Error::TargetExtendsCycle(String::from("a -> b -> a")))
+        );
+        assert_eq!(
+            cfg.get_target("lost"),
+            Err(Error::TargetExtendsNotFound(
+                String::from("lost"),
+                String::from("missing"),
+                Hint::TargetsList
+            ))
+        );
+        assert_eq!(
+            cfg.get_target("empty"),
+            Err(Error::TargetMissingCommand(String::from("empty")))
+        );
+        assert_eq!(cfg.get_target("unknown"), Ok(None));
+    }
+
     #[test]
     fn linked_configs() {
         Configs::new()
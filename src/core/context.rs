@@ -391,7 +391,7 @@ impl Context {
         target: &Option<String>,
         required: bool,
         is_build: bool,
-    ) -> Result<Option<&Target>, Error> {
+    ) -> Result<Option<Target>, Error> {
         let env_target = Environment::read(environment::ORBIT_TARGET);
         let target = match target {
             Some(t) => Some(t),
@@ -412,8 +412,8 @@ impl Context {
         };
         match target {
             // verify the target name matches
-            Some(name) => match self.get_config().get_targets().get(name.as_str()) {
-                Some(&t) => Ok(Some(t)),
+            Some(name) => match self.get_config().get_target(name.as_str())? {
+                Some(t) => Ok(Some(t)),
                 None => Err(Error::TargetNotFound(name.to_string(), Hint::TargetsList)),
             },
             None => match required {
@@ -0,0 +1,176 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::util::anyerror::Fault;
+use serde_derive::Serialize;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+pub const EXPLAIN_FILE: &str = "explain.json";
+
+/// The reason a hdl file was written to the blueprint.
+#[derive(Debug, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum Reason {
+    /// A unit defined in the file is referenced by a selected unit.
+    Reference,
+    /// The file was merged together with the files of a referenced unit.
+    Merge,
+    /// The file is only included because of `--all`.
+    All,
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Reference => "reference",
+                Self::Merge => "merge",
+                Self::All => "all",
+            }
+        )
+    }
+}
+
+/// Describes why a single hdl file is in the blueprint.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Explanation {
+    file: String,
+    library: String,
+    ip: String,
+    reason: Reason,
+    units: Vec<String>,
+    path: Vec<String>,
+    merged_with: Option<String>,
+}
+
+impl Explanation {
+    /// Creates a new explanation for the hdl `file` from `library` owned by `ip`.
+    ///
+    /// The `units` are the design units associated with the file, and the `path`
+    /// is the shortest chain of unit references starting from a selected unit.
+    pub fn new(
+        file: &str,
+        library: &str,
+        ip: &str,
+        reason: Reason,
+        units: Vec<String>,
+        path: Vec<String>,
+        merged_with: Option<String>,
+    ) -> Self {
+        Self {
+            file: file.to_string(),
+            library: library.to_string(),
+            ip: ip.to_string(),
+            reason: reason,
+            units: units,
+            path: path,
+            merged_with: merged_with,
+        }
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} ({})", self.file, self.ip)?;
+        match self.reason {
+            Reason::Reference => write!(f, "    {}: {}", self.reason, self.path.join(" -> ")),
+            Reason::Merge => write!(
+                f,
+                "    {}: {} (with {})",
+                self.reason,
+                self.path.join(" -> "),
+                self.merged_with.as_ref().unwrap_or(&String::new())
+            ),
+            Reason::All => write!(f, "    {}: not referenced by a selected unit", self.reason),
+        }
+    }
+}
+
+/// The collection of explanations for every hdl file in a blueprint.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Explain {
+    files: Vec<Explanation>,
+}
+
+impl Explain {
+    pub fn new() -> Self {
+        Self { files: Vec::new() }
+    }
+
+    /// Add the next explanation `e` to the list.
+    pub fn add(&mut self, e: Explanation) {
+        self.files.push(e);
+    }
+
+    /// Writes the explanations as json to a file next to the blueprint in the
+    /// `output_path`.
+    pub fn write(&self, output_path: &PathBuf) -> Result<PathBuf, Fault> {
+        let explain_path = output_path.join(EXPLAIN_FILE);
+        let mut data = serde_json::to_string_pretty(&self)?;
+        data.push('\n');
+        std::fs::write(&explain_path, data)?;
+        Ok(explain_path)
+    }
+}
+
+impl Display for Explain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for e in &self.files {
+            writeln!(f, "{}", e)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_explanation() {
+        let e = Explanation::new(
+            "/a/arch.vhd",
+            "work",
+            "gates:1.0.0",
+            Reason::Merge,
+            vec![String::from("work.and_gate")],
+            vec![String::from("work.top"), String::from("work.and_gate")],
+            Some(String::from("/a/and_gate.vhd")),
+        );
+        assert_eq!(
+            e.to_string(),
+            "/a/arch.vhd (gates:1.0.0)\n    merge: work.top -> work.and_gate (with /a/and_gate.vhd)"
+        );
+
+        let e = Explanation::new(
+            "/a/extra.vhd",
+            "work",
+            "gates:1.0.0",
+            Reason::All,
+            vec![String::from("work.extra")],
+            Vec::new(),
+            None,
+        );
+        assert_eq!(
+            e.to_string(),
+            "/a/extra.vhd (gates:1.0.0)\n    all: not referenced by a selected unit"
+        );
+    }
+}
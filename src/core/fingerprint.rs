@@ -0,0 +1,142 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! A fingerprint of everything that determines the outcome of running a target.
+//!
+//! A build is fresh when the fingerprint of its inputs matches the fingerprint
+//! recorded after the last successful run in the same output directory.
+
+use crate::util::anyerror::Fault;
+use crate::util::environment::Environment;
+use crate::util::sha256::{self, Sha256Hash};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// The file that stores the fingerprint of the last successful run within the
+/// target's output directory.
+pub const FINGERPRINT_FILE: &str = ".orbit-fingerprint";
+
+#[derive(Debug, PartialEq)]
+pub struct Fingerprint(Sha256Hash);
+
+impl Fingerprint {
+    /// Computes the fingerprint of running a target.
+    ///
+    /// The inputs are the `blueprint` contents, the contents of every file in
+    /// `files`, the resolved `target` definition, the `command` override, the
+    /// command-line `args`, and the environment variables `envs` passed to the
+    /// process.
+    pub fn compute(
+        blueprint: &str,
+        files: &[String],
+        target: &str,
+        command: &Option<String>,
+        args: &[String],
+        envs: &Environment,
+    ) -> Self {
+        let mut data = String::new();
+        data.push_str("[blueprint]\n");
+        data.push_str(blueprint);
+        data.push_str("\n[files]\n");
+        for f in files {
+            // a missing file still contributes its path to the fingerprint
+            let sum = match std::fs::read(f) {
+                Ok(bytes) => sha256::compute_sha256(&bytes).to_string(),
+                Err(_) => String::from("-"),
+            };
+            data.push_str(&format!("{}\t{}\n", f, sum));
+        }
+        data.push_str("[target]\n");
+        data.push_str(target);
+        data.push_str("\n[command]\n");
+        data.push_str(command.as_ref().map(|c| c.as_str()).unwrap_or(""));
+        data.push_str("\n[args]\n");
+        for a in args {
+            data.push_str(&format!("{:?}\n", a));
+        }
+        data.push_str("[env]\n");
+        for e in envs.iter() {
+            data.push_str(&format!("{}={:?}\n", e.get_key(), e.get_value()));
+        }
+        Self(sha256::compute_sha256(data.as_bytes()))
+    }
+
+    /// Reads the fingerprint recorded in the `output_path`, if one exists.
+    pub fn read(output_path: &PathBuf) -> Option<Self> {
+        let text = std::fs::read_to_string(output_path.join(FINGERPRINT_FILE)).ok()?;
+        Some(Self(Sha256Hash::from_str(text.trim()).ok()?))
+    }
+
+    /// Checks if this fingerprint matches the one recorded in the `output_path`.
+    pub fn is_fresh(&self, output_path: &PathBuf) -> bool {
+        Self::read(output_path).as_ref() == Some(self)
+    }
+
+    /// Records this fingerprint in the `output_path`.
+    pub fn write(&self, output_path: &PathBuf) -> Result<(), Fault> {
+        std::fs::write(output_path.join(FINGERPRINT_FILE), format!("{}\n", self.0))?;
+        Ok(())
+    }
+
+    /// Removes any fingerprint recorded in the `output_path`.
+    pub fn remove(output_path: &PathBuf) -> Result<(), Fault> {
+        let path = output_path.join(FINGERPRINT_FILE);
+        if path.exists() == true {
+            std::fs::remove_file(&path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::environment::EnvVar;
+
+    #[test]
+    fn detect_changed_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().to_path_buf();
+        let file = dir.join("top.vhd");
+        std::fs::write(&file, "entity top is end entity;").unwrap();
+        let files = vec![file.to_str().unwrap().to_string()];
+        let envs = Environment::new().add(EnvVar::with("ORBIT_TARGET", "foo"));
+
+        let fp = Fingerprint::compute("bp", &files, "tgt", &None, &[], &envs);
+        assert_eq!(fp.is_fresh(&dir), false);
+        fp.write(&dir).unwrap();
+        assert_eq!(fp.is_fresh(&dir), true);
+
+        // identical inputs produce the same fingerprint
+        let same = Fingerprint::compute("bp", &files, "tgt", &None, &[], &envs);
+        assert_eq!(same.is_fresh(&dir), true);
+
+        // changing any input produces a different fingerprint
+        let args = vec![String::from("--gui")];
+        let fp2 = Fingerprint::compute("bp", &files, "tgt", &None, &args, &envs);
+        assert_eq!(fp2.is_fresh(&dir), false);
+        let envs2 = Environment::new().add(EnvVar::with("ORBIT_TARGET", "bar"));
+        let fp2 = Fingerprint::compute("bp", &files, "tgt", &None, &[], &envs2);
+        assert_eq!(fp2.is_fresh(&dir), false);
+        std::fs::write(&file, "entity top is end;").unwrap();
+        let fp2 = Fingerprint::compute("bp", &files, "tgt", &None, &[], &envs);
+        assert_eq!(fp2.is_fresh(&dir), false);
+
+        Fingerprint::remove(&dir).unwrap();
+        assert_eq!(fp.is_fresh(&dir), false);
+    }
+}
@@ -0,0 +1,209 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Overrides for the generics (parameters) of the top-level unit set from the
+//! command-line.
+
+use crate::core::lang::ir::HdlIr;
+use crate::core::lang::Lang;
+use crate::error::{Error, Hint};
+use crate::util::seqalin;
+use crate::util::strcmp;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// The maximum edit distance for a declared generic to be suggested in place of
+/// an unknown name.
+const SUGGESTION_THRESHOLD: usize = 4;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Generic {
+    name: String,
+    value: String,
+}
+
+#[derive(Debug)]
+pub enum GenericError {
+    MissingSeparator(char),
+    EmptyName,
+}
+
+impl std::error::Error for GenericError {}
+
+impl std::fmt::Display for GenericError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::EmptyName => write!(f, "empty name"),
+            Self::MissingSeparator(c) => write!(f, "missing separator '{}'", c),
+        }
+    }
+}
+
+impl FromStr for Generic {
+    type Err = GenericError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = match s.split_once('=') {
+            Some(pair) => pair,
+            None => return Err(Self::Err::MissingSeparator('=')),
+        };
+        let name = name.trim();
+        // name cannot be empty (the value may be, such as an empty string)
+        if name.is_empty() {
+            return Err(Self::Err::EmptyName);
+        }
+        Ok(Self {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+    }
+}
+
+impl Generic {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+}
+
+/// Checks that every generic in `generics` is declared in the `unit`'s
+/// interface, and returns the generics named as they are declared.
+///
+/// Names are compared without case sensitivity for VHDL units. An unknown name
+/// suggests the most similar declared generic, if one is close enough.
+pub fn verify(generics: &[Generic], unit: &HdlIr) -> Result<Vec<Generic>, Error> {
+    let declared = unit.get_generic_names();
+    let ignore_case = unit.get_language() == &Lang::Vhdl;
+    let mut verified = Vec::with_capacity(generics.len());
+    for g in generics {
+        let found = declared.iter().find(|d| match ignore_case {
+            true => strcmp::cmp_ascii_ignore_case(d, g.get_name()),
+            false => *d == &g.get_name(),
+        });
+        if let Some(d) = found {
+            verified.push(Generic {
+                name: d.to_string(),
+                value: g.value.clone(),
+            });
+            continue;
+        }
+        let normalize = |s: &str| match ignore_case {
+            true => s.to_ascii_lowercase(),
+            false => s.to_string(),
+        };
+        let bank: Vec<String> = declared.iter().map(|d| normalize(d)).collect();
+        let hint = match seqalin::sel_min_edit_str(
+            &normalize(g.get_name()),
+            &bank,
+            SUGGESTION_THRESHOLD,
+        ) {
+            // suggest the generic as it is written in the interface
+            Some(s) => {
+                let i = bank.iter().position(|b| b == s).unwrap();
+                Hint::DidYouMean(declared[i].to_string())
+            }
+            None => Hint::ShowUnitGenerics(unit.get_identifier().to_string()),
+        };
+        return Err(Error::GenericNotFound(
+            unit.get_identifier().to_string(),
+            g.get_name().to_string(),
+            hint,
+        ));
+    }
+    Ok(verified)
+}
+
+/// Collects the generics into a map from name to value.
+///
+/// A generic that is set more than once keeps its last value.
+pub fn to_map(generics: &[Generic]) -> BTreeMap<&str, &str> {
+    generics
+        .iter()
+        .map(|g| (g.get_name(), g.get_value()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        let g = Generic::from_str("WIDTH=16").unwrap();
+        assert_eq!(g.get_name(), "WIDTH");
+        assert_eq!(g.get_value(), "16");
+        assert_eq!(Generic::from_str("INIT=a=b").unwrap().get_value(), "a=b");
+        assert_eq!(Generic::from_str("NAME=").unwrap().get_value(), "");
+        assert_eq!(Generic::from_str("WIDTH").is_err(), true);
+        assert_eq!(Generic::from_str("=16").is_err(), true);
+    }
+
+    #[test]
+    fn verify_against_interface() {
+        let unit = HdlIr::from_str(
+            r#"{"identifier":"fifo","generics":[{"identifier":"WIDTH","mode":"in","type":"positive","default":"8"},{"identifier":"DEPTH","mode":"in","type":"positive","default":"4"}],"ports":[],"architectures":[],"language":"vhdl"}"#,
+        )
+        .unwrap();
+        let set = |s: &[&str]| -> Vec<Generic> {
+            s.iter().map(|g| Generic::from_str(g).unwrap()).collect()
+        };
+
+        assert_eq!(
+            verify(&set(&["WIDTH=16", "depth=2"]), &unit).unwrap(),
+            set(&["WIDTH=16", "DEPTH=2"])
+        );
+        assert_eq!(
+            verify(&set(&["WIDHT=16"]), &unit).unwrap_err().to_string(),
+            Error::GenericNotFound(
+                String::from("fifo"),
+                String::from("WIDHT"),
+                Hint::DidYouMean(String::from("WIDTH"))
+            )
+            .to_string()
+        );
+        assert_eq!(
+            verify(&set(&["widht=16"]), &unit).unwrap_err().to_string(),
+            Error::GenericNotFound(
+                String::from("fifo"),
+                String::from("widht"),
+                Hint::DidYouMean(String::from("WIDTH"))
+            )
+            .to_string()
+        );
+        assert_eq!(
+            verify(&set(&["SPEED=1"]), &unit).unwrap_err().to_string(),
+            Error::GenericNotFound(
+                String::from("fifo"),
+                String::from("SPEED"),
+                Hint::ShowUnitGenerics(String::from("fifo"))
+            )
+            .to_string()
+        );
+
+        let generics = set(&["WIDTH=16", "DEPTH=2", "WIDTH=32"]);
+        let map = to_map(&generics);
+        assert_eq!(map, BTreeMap::from([("DEPTH", "2"), ("WIDTH", "32")]));
+
+        // the last value wins regardless of how a vhdl generic is spelled
+        let generics = verify(&set(&["WIDTH=8", "width=16"]), &unit).unwrap();
+        let map = to_map(&generics);
+        assert_eq!(map, BTreeMap::from([("WIDTH", "16")]));
+    }
+}
@@ -28,15 +28,16 @@ use super::catalog::PkgName;
 use super::iparchive::IpArchive;
 use super::ippointer::IpPointer;
 use super::lang;
+use super::lang::cache::SymbolCache;
 use super::lang::LangIdentifier;
 use super::lang::LangUnit;
 use super::lockfile::LockFile;
 use super::lockfile::IP_LOCK_FILE;
 use super::manifest::FromFile;
-use super::version::PartialVersion;
 use super::visibility::VipList;
 use super::visibility::Visibility;
 use crate::core::lockfile::LockEntry;
+use crate::core::manifest::FileAttributes;
 use crate::core::manifest::IP_MANIFEST_FILE;
 use crate::core::manifest::ORBIT_METADATA_FILE;
 use crate::core::manifest::ORBIT_SUM_FILE;
@@ -113,6 +114,39 @@ pub struct Ip {
     lock: LockFile,
     /// The UUID for the [Ip].
     uuid: Uuid,
+    /// The cache of previously parsed symbols for the [Ip]'s files.
+    symbols: Option<SymbolCache>,
+    /// The compiled file patterns of the [Ip]'s attributes.
+    attributes: AttributeMatchers,
+}
+
+/// The `files` patterns of each attributes entry in a manifest, compiled in the
+/// same order as the entries.
+#[derive(Debug, Default)]
+struct AttributeMatchers(Vec<VipList>);
+
+impl PartialEq for AttributeMatchers {
+    /// Matchers are compiled from the manifest, so they are only compared
+    /// through their owning [Ip].
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl AttributeMatchers {
+    /// Compiles the patterns of every attributes entry in `man` relative to
+    /// the `root`.
+    fn compile(root: &PathBuf, man: &Manifest) -> Result<Self, Error> {
+        man.get_attributes()
+            .iter()
+            .enumerate()
+            .map(|(i, attrs)| {
+                VipList::new(root, &Some(attrs.get_files().clone()))
+                    .map_err(|e| Error::AttributesPatternInvalid(i + 1, LastError(e.to_string())))
+            })
+            .collect::<Result<Vec<VipList>, Error>>()
+            .map(|list| Self(list))
+    }
 }
 
 impl From<IpPointer> for Ip {
@@ -124,6 +158,8 @@ impl From<IpPointer> for Ip {
             root: PathBuf::new(),
             data: man,
             lock: LockFile::new(),
+            symbols: None,
+            attributes: AttributeMatchers::default(),
         }
     }
 }
@@ -135,7 +171,7 @@ impl From<IpArchive> for Ip {
             Some(entry) => entry.get_uuid().clone(),
             None => match lock.get(
                 man.get_ip().get_name(),
-                &man.get_ip().get_version().to_partial_version(),
+                &VersionReq::from(man.get_ip().get_version().to_partial_version()),
             ) {
                 Some(entry) => entry.get_uuid().clone(),
                 None => Uuid::new(),
@@ -147,6 +183,8 @@ impl From<IpArchive> for Ip {
             data: man,
             lock: lock,
             uuid: uuid,
+            symbols: None,
+            attributes: AttributeMatchers::default(),
         }
     }
 }
@@ -200,6 +238,15 @@ impl Ip {
         &self.lock
     }
 
+    /// Stores the `cache` to use when parsing the [Ip]'s files.
+    pub fn set_symbol_cache(&mut self, cache: SymbolCache) {
+        self.symbols = Some(cache);
+    }
+
+    pub fn get_symbol_cache(&self) -> Option<&SymbolCache> {
+        self.symbols.as_ref()
+    }
+
     pub fn get_uuid(&self) -> &Uuid {
         &self.uuid
     }
@@ -279,6 +326,9 @@ impl Ip {
         // verify the public list is okay
         VipList::new(&root, man.get_ip().get_publics())?;
 
+        // verify the attributes' file patterns are okay
+        let attributes = AttributeMatchers::compile(&root, &man)?;
+
         if is_working_ip == true {
             // verify there are no files that created by user that are reserved for orbit's internal use
             match Self::check_illegal_files(&root) {
@@ -323,6 +373,8 @@ impl Ip {
             data: man,
             lock: lock,
             uuid: uuid,
+            symbols: None,
+            attributes: attributes,
         })
     }
 
@@ -441,7 +493,7 @@ impl Ip {
     pub fn can_use_lock(&self, catalog: &Catalog) -> bool {
         let target = self.get_lock().get(
             self.get_man().get_ip().get_name(),
-            &self.get_man().get_ip().get_version().to_partial_version(),
+            &VersionReq::from(self.get_man().get_ip().get_version().to_partial_version()),
         );
         let target_is_ok = match target {
             Some(entry) => entry.matches_target(&LockEntry::from((self, true)), &catalog),
@@ -584,7 +636,7 @@ impl Ip {
                 // collect all files
                 let files = self.gather_current_files();
 
-                let mut map = lang::collect_units(&files)?;
+                let mut map = lang::collect_units(&files, self.get_symbol_cache())?;
 
                 // work to remove files that are totally private
                 if public_list.exists() == true {
@@ -672,6 +724,25 @@ impl Ip {
         VipList::new(&self.root, &self.get_man().get_ip().get_exclude())
     }
 
+    /// Resolves the attributes assigned to `file` by the manifest.
+    ///
+    /// Every attributes entry with a pattern matching the file is applied in
+    /// order, so later entries take precedence over earlier entries.
+    pub fn get_file_attributes(&self, file: &str) -> FileAttributes {
+        let mut result = FileAttributes::default();
+        for (attrs, vip) in self
+            .get_man()
+            .get_attributes()
+            .iter()
+            .zip(self.attributes.0.iter())
+        {
+            if vip.is_included(file) == true {
+                result.merge(attrs.get_values());
+            }
+        }
+        result
+    }
+
     pub fn gather_current_files(&self) -> Vec<String> {
         let inc = match self.get_include_list() {
             Ok(vip) => match vip.exists() {
@@ -848,14 +919,14 @@ impl Serialize for IpSpec {
     }
 }
 
-use crate::core::version::AnyVersion;
+use crate::core::version::{AnyVersion, VersionReq};
 
 #[derive(PartialEq, Clone, Hash, Eq)]
 pub struct PartialIpSpec(PkgPart, Option<Uuid>, AnyVersion);
 
 impl PartialIpSpec {
-    pub fn new(name: PkgPart, uuid: Option<Uuid>, version: PartialVersion) -> Self {
-        Self(name, uuid, AnyVersion::Specific(version))
+    pub fn new(name: PkgPart, uuid: Option<Uuid>, version: VersionReq) -> Self {
+        Self(name, uuid, AnyVersion::from(&version))
     }
 
     pub fn get_name(&self) -> &PkgPart {
@@ -1015,6 +1086,80 @@ mod test {
         )
     }
 
+    #[test]
+    fn resolve_file_attributes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        std::fs::write(
+            root.join(IP_MANIFEST_FILE),
+            r#"[ip]
+name = "attrs"
+uuid = "0000000000000000000000000"
+version = "0.1.0"
+
+[[attributes]]
+files = ["*.sv"]
+std = "2012"
+
+[[attributes]]
+files = ["/sim"]
+std = "2017"
+defines = { SIM = true }
+"#,
+        )
+        .unwrap();
+        let ip = Ip::load(root.clone(), true, false).unwrap();
+
+        let file = root.join("rtl").join("alu.sv");
+        let attrs = ip.get_file_attributes(file.to_str().unwrap());
+        assert_eq!(attrs.get_std(), &Some(String::from("2012")));
+        assert_eq!(attrs.get_defines().is_empty(), true);
+
+        // later entries take precedence
+        let file = root.join("sim").join("alu_tb.sv");
+        let attrs = ip.get_file_attributes(file.to_str().unwrap());
+        assert_eq!(attrs.get_std(), &Some(String::from("2017")));
+        assert_eq!(attrs.get_defines().len(), 1);
+
+        let file = root.join("rtl").join("alu.vhd");
+        assert_eq!(
+            ip.get_file_attributes(file.to_str().unwrap()),
+            FileAttributes::default()
+        );
+
+        // an anchored pattern only matches from the ip's root directory
+        let file = root.join("rtl").join("sim").join("alu_tb.sv");
+        let attrs = ip.get_file_attributes(file.to_str().unwrap());
+        assert_eq!(attrs.get_std(), &Some(String::from("2012")));
+        assert_eq!(attrs.get_defines().is_empty(), true);
+
+        // invalid patterns are reported when the manifest loads
+        std::fs::write(
+            root.join(IP_MANIFEST_FILE),
+            r#"[ip]
+name = "attrs"
+uuid = "0000000000000000000000000"
+version = "0.1.0"
+
+[[attributes]]
+files = ["*.sv"]
+std = "2012"
+
+[[attributes]]
+files = ["sim/[ab"]
+std = "2017"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Ip::load(root.clone(), true, false)
+                .unwrap_err()
+                .to_string()
+                .starts_with("attributes entry 2 in the ip manifest has an invalid files pattern"),
+            true
+        );
+    }
+
     #[test]
     fn from_str_ip_spec() {
         let ip = format!(
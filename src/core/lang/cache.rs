@@ -0,0 +1,319 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! A per-file cache of the symbols parsed from HDL source code.
+//!
+//! Entries are keyed by the language and the SHA-256 hash of the file's
+//! contents, so a file is only parsed again once its contents change.
+
+use super::sv::error::SystemVerilogError;
+use super::sv::symbols::{SystemVerilogParser, SystemVerilogSymbol};
+use super::verilog::error::VerilogError;
+use super::verilog::symbols::{VerilogParser, VerilogSymbol};
+use super::vhdl::error::VhdlError;
+use super::vhdl::symbols::{VHDLParser, VhdlSymbol};
+use super::Lang;
+use crate::util::anyerror::Fault;
+use crate::util::sha256;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize as _, Serialize};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// The file that stores the cached symbols within the target directory.
+pub const SYMBOL_CACHE_FILE: &str = ".orbit-symbols";
+
+/// The version of orbit that wrote the cache. Symbols written by any other
+/// version are discarded because their structure may have changed.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Serialize, Deserialize)]
+struct CacheData {
+    version: String,
+    files: HashMap<String, Value>,
+}
+
+#[derive(Debug)]
+pub struct SymbolCache {
+    path: PathBuf,
+    /// The symbols previously stored in the cache file.
+    stored: HashMap<String, Value>,
+    /// The symbols accessed or added during the current run.
+    current: RefCell<HashMap<String, Value>>,
+}
+
+impl PartialEq for SymbolCache {
+    /// Caches are only compared by their location to keep comparing their
+    /// owning [Ip][crate::core::ip::Ip] cheap.
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+impl SymbolCache {
+    /// Opens the cache in the directory `dir`.
+    ///
+    /// The cache starts empty if the file is missing, cannot be read, or was
+    /// written by a different version of orbit.
+    pub fn open(dir: &PathBuf) -> Self {
+        let path = dir.join(SYMBOL_CACHE_FILE);
+        let stored = match std::fs::read_to_string(&path) {
+            Ok(text) => match serde_json::from_str::<CacheData>(&text) {
+                Ok(data) => match data.version == VERSION {
+                    true => data.files,
+                    false => HashMap::new(),
+                },
+                Err(_) => HashMap::new(),
+            },
+            Err(_) => HashMap::new(),
+        };
+        Self {
+            path: path,
+            stored: stored,
+            current: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Writes the entries accessed during the current run to the cache file.
+    ///
+    /// Entries for file contents that were not seen are dropped, so the cache
+    /// does not grow as files are edited.
+    pub fn save(&self) -> Result<(), Fault> {
+        let current = self.current.borrow();
+        // skip writing when nothing was added or removed
+        if current.len() == self.stored.len()
+            && current.keys().all(|k| self.stored.contains_key(k)) == true
+        {
+            return Ok(());
+        }
+        let data = CacheData {
+            version: VERSION.to_string(),
+            files: current.clone(),
+        };
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string(&data)?)?;
+        Ok(())
+    }
+
+    /// Returns the symbols for the source code `contents`, only calling `parse`
+    /// when no entry exists for the contents.
+    fn fetch<T, E>(
+        &self,
+        lang: Lang,
+        contents: &str,
+        parse: impl FnOnce(&str) -> Result<Vec<T>, E>,
+    ) -> Result<Vec<T>, E>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let key = format!(
+            "{}:{}",
+            lang,
+            sha256::compute_sha256(contents.as_bytes()).to_string()
+        );
+        let mut current = self.current.borrow_mut();
+        if let Some(value) = current.get(&key) {
+            if let Ok(symbols) = Vec::<T>::deserialize(value) {
+                return Ok(symbols);
+            }
+        } else if let Some(value) = self.stored.get(&key) {
+            if let Ok(symbols) = Vec::<T>::deserialize(value) {
+                current.insert(key, value.clone());
+                return Ok(symbols);
+            }
+        }
+        let symbols = parse(contents)?;
+        if let Ok(value) = serde_json::to_value(&symbols) {
+            current.insert(key, value);
+        }
+        Ok(symbols)
+    }
+}
+
+/// Reads the VHDL symbols from `contents`, using the `cache` when available.
+pub fn read_vhdl(
+    cache: Option<&SymbolCache>,
+    contents: &str,
+) -> Result<Vec<VhdlSymbol>, VhdlError> {
+    let parse = |s: &str| Ok(VHDLParser::read(s)?.into_symbols());
+    match cache {
+        Some(c) => c.fetch(Lang::Vhdl, contents, parse),
+        None => parse(contents),
+    }
+}
+
+/// Reads the Verilog symbols from `contents`, using the `cache` when available.
+pub fn read_verilog(
+    cache: Option<&SymbolCache>,
+    contents: &str,
+) -> Result<Vec<VerilogSymbol>, VerilogError> {
+    let parse = |s: &str| Ok(VerilogParser::read(s)?.into_symbols());
+    match cache {
+        Some(c) => c.fetch(Lang::Verilog, contents, parse),
+        None => parse(contents),
+    }
+}
+
+/// Reads the SystemVerilog symbols from `contents`, using the `cache` when
+/// available.
+pub fn read_systemverilog(
+    cache: Option<&SymbolCache>,
+    contents: &str,
+) -> Result<Vec<SystemVerilogSymbol>, SystemVerilogError> {
+    let parse = |s: &str| Ok(SystemVerilogParser::read(s)?.into_symbols());
+    match cache {
+        Some(c) => c.fetch(Lang::SystemVerilog, contents, parse),
+        None => parse(contents),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reuse_cached_symbols() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().to_path_buf();
+        let code =
+            "entity foo is end entity;\n\narchitecture rtl of foo is begin end architecture;";
+
+        let cache = SymbolCache::open(&dir);
+        let parsed = read_vhdl(Some(&cache), code).unwrap();
+        cache.save().unwrap();
+
+        // the cached symbols are returned without parsing
+        let cache = SymbolCache::open(&dir);
+        let cached = cache
+            .fetch(Lang::Vhdl, code, |_| Err::<Vec<VhdlSymbol>, ()>(()))
+            .unwrap();
+        assert_eq!(cached, parsed);
+        assert_eq!(cached, read_vhdl(None, code).unwrap());
+
+        // changing the contents requires a new parse
+        assert_eq!(
+            cache
+                .fetch(Lang::Vhdl, "entity bar is end;", |_| {
+                    Err::<Vec<VhdlSymbol>, ()>(())
+                })
+                .is_err(),
+            true
+        );
+    }
+
+    #[test]
+    fn reuse_cached_verilog_symbols() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().to_path_buf();
+        let code = "module adder #(parameter WIDTH = 8) (
+    input wire [WIDTH-1:0] a, b,
+    output wire [WIDTH-1:0] y
+);
+    assign y = a + b;
+endmodule
+
+module top(input wire [7:0] a, output wire [7:0] y);
+    adder #(.WIDTH(8)) u0 (.a(a), .b(a), .y(y));
+endmodule
+";
+        let cache = SymbolCache::open(&dir);
+        let parsed = read_verilog(Some(&cache), code).unwrap();
+        assert_eq!(parsed.len(), 2);
+        cache.save().unwrap();
+
+        // the cached symbols are returned without parsing
+        let cache = SymbolCache::open(&dir);
+        let cached = cache
+            .fetch(Lang::Verilog, code, |_| Err::<Vec<VerilogSymbol>, ()>(()))
+            .unwrap();
+        assert_eq!(cached, parsed);
+        assert_eq!(cached, read_verilog(None, code).unwrap());
+
+        // the same contents are stored separately for each language
+        assert_eq!(
+            cache
+                .fetch(Lang::SystemVerilog, code, |_| {
+                    Err::<Vec<SystemVerilogSymbol>, ()>(())
+                })
+                .is_err(),
+            true
+        );
+    }
+
+    #[test]
+    fn reuse_cached_systemverilog_symbols() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().to_path_buf();
+        let code = "package types_pkg;
+    typedef logic [7:0] byte_t;
+endpackage
+
+interface bus_if;
+    logic valid;
+    modport source (output valid);
+endinterface
+
+module fifo import types_pkg::*; #(parameter int DEPTH = 4) (
+    input logic clk,
+    input byte_t data,
+    bus_if.source bus
+);
+    always_ff @(posedge clk) bus.valid <= 1'b1;
+endmodule
+";
+        let cache = SymbolCache::open(&dir);
+        let parsed = read_systemverilog(Some(&cache), code).unwrap();
+        assert_eq!(parsed.len(), 3);
+        cache.save().unwrap();
+
+        // the cached symbols are returned without parsing
+        let cache = SymbolCache::open(&dir);
+        let cached = cache
+            .fetch(Lang::SystemVerilog, code, |_| {
+                Err::<Vec<SystemVerilogSymbol>, ()>(())
+            })
+            .unwrap();
+        assert_eq!(cached, parsed);
+        assert_eq!(cached, read_systemverilog(None, code).unwrap());
+    }
+
+    #[test]
+    fn discard_other_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().to_path_buf();
+        let code = "entity foo is end entity;";
+
+        let cache = SymbolCache::open(&dir);
+        read_vhdl(Some(&cache), code).unwrap();
+        cache.save().unwrap();
+
+        // rewrite the cache as if it came from an older version
+        let path = dir.join(SYMBOL_CACHE_FILE);
+        let mut data: CacheData =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        data.version = String::from("0.0.0");
+        std::fs::write(&path, serde_json::to_string(&data).unwrap()).unwrap();
+
+        let cache = SymbolCache::open(&dir);
+        assert_eq!(cache.stored.is_empty(), true);
+    }
+}
@@ -17,13 +17,15 @@
 
 // Reference for mixing languages: https://docs.amd.com/r/en-US/ug901-vivado-synthesis/Mixed-Language-Support
 
-use serde_derive::Deserialize;
+use serde_derive::{Deserialize, Serialize};
 
 use super::vhdl::error::VhdlError;
 use super::Lang;
 use std::str::FromStr;
 
 use crate::core::lang::sv::symbols::module::Module;
+use crate::core::lang::verilog::interface::Port as VlogPort;
+use crate::core::lang::vhdl::interface::InterfaceDeclarations;
 use crate::core::lang::vhdl::symbols::entity::Entity;
 
 use super::lexer::Position;
@@ -31,30 +33,24 @@ use super::sv::token::token::SystemVerilogToken as Svt;
 use super::vhdl::token::VhdlToken as Vvt;
 use crate::core::lang::lexer::Token;
 
-#[derive(Deserialize)]
-struct Generic {
+/// A single generic (parameter) or port in a design unit's interface.
+#[derive(Serialize, Deserialize)]
+struct Declaration {
     identifier: String,
-    mode: String,
-    #[serde(rename = "type")]
-    kind: Option<String>,
-    default: Option<String>,
-}
-
-#[derive(Deserialize)]
-struct Port {
-    identifier: String,
-    mode: String,
+    mode: Option<String>,
     #[serde(rename = "type")]
     kind: Option<String>,
     default: Option<String>,
 }
 
 /// Intermediate representation to convert HDL between the various languages.
-#[derive(Deserialize)]
+///
+/// This is also the structure written for a design unit's json output.
+#[derive(Serialize, Deserialize)]
 pub struct HdlIr {
     identifier: String,
-    generics: Vec<Generic>,
-    ports: Vec<Port>,
+    generics: Vec<Declaration>,
+    ports: Vec<Declaration>,
     architectures: Vec<String>,
     language: Lang,
 }
@@ -69,15 +65,73 @@ impl FromStr for HdlIr {
 
 impl HdlIr {
     pub fn from_vhdl_entity(entity: &Entity) -> Self {
-        Self::from_str(&serde_json::to_string(entity).unwrap()).unwrap()
+        let into_decls = |decls: &InterfaceDeclarations| {
+            decls
+                .0
+                .iter()
+                .map(|d| Declaration {
+                    identifier: d.get_name().as_str().to_string(),
+                    mode: Some(d.get_mode().to_lowercase_string()),
+                    kind: Some(d.get_type().to_bland_string()),
+                    default: d.get_default().to_bland_string(),
+                })
+                .collect()
+        };
+        Self {
+            identifier: entity.get_name().as_str().to_string(),
+            generics: into_decls(&entity.get_generics().0),
+            ports: into_decls(&entity.get_ports().0),
+            architectures: entity
+                .get_architectures()
+                .iter()
+                .map(|a| a.get_name().as_str().to_string())
+                .collect(),
+            language: Lang::Vhdl,
+        }
     }
 
     pub fn from_sv_module(module: &Module) -> Self {
-        Self::from_str(&serde_json::to_string(module).unwrap()).unwrap()
+        Self::from_vlog_module(module)
     }
 
     pub fn from_vlog_module(module: &Module) -> Self {
-        Self::from_str(&serde_json::to_string(module).unwrap()).unwrap()
+        let into_decls = |decls: &Vec<VlogPort>| {
+            decls
+                .iter()
+                .map(|d| Declaration {
+                    identifier: d.get_name().as_str().to_string(),
+                    mode: d.get_mode().as_ref().map(|k| k.to_string()),
+                    kind: d.get_datatype().to_bland_string(),
+                    default: d.get_default().to_bland_string(),
+                })
+                .collect()
+        };
+        Self {
+            identifier: module.get_name().as_str().to_string(),
+            generics: into_decls(module.get_parameters()),
+            ports: into_decls(module.get_ports()),
+            architectures: Vec::new(),
+            language: match module.get_language() {
+                "systemverilog" => Lang::SystemVerilog,
+                _ => Lang::Verilog,
+            },
+        }
+    }
+
+    pub fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    pub fn get_language(&self) -> &Lang {
+        &self.language
+    }
+
+    /// Collects the names of the unit's generics (parameters).
+    pub fn get_generic_names(&self) -> Vec<&str> {
+        self.generics
+            .iter()
+            .map(|g| g.identifier.as_str())
+            .collect()
     }
 }
 
@@ -15,6 +15,7 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use serde_derive::{Deserialize, Serialize};
 use std::fmt::Display;
 use std::iter::Peekable;
 
@@ -96,7 +97,7 @@ impl<T: Display> Display for TokenError<T> {
 
 use std::cmp::Ordering;
 
-#[derive(Debug, PartialEq, Clone, Ord, Eq)]
+#[derive(Debug, PartialEq, Clone, Ord, Eq, Serialize, Deserialize)]
 /// (Line, Col)
 pub struct Position(usize, usize);
 
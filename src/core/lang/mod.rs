@@ -15,6 +15,7 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+pub mod cache;
 pub mod highlight;
 pub mod sv;
 pub mod verilog;
@@ -34,6 +35,7 @@ use crate::error::Hint;
 use crate::util::anyerror::AnyError;
 use crate::util::anyerror::CodeFault;
 use crate::util::filesystem;
+use cache::SymbolCache;
 use lexer::Position;
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
@@ -401,7 +403,7 @@ impl Display for LangUnit {
     }
 }
 
-#[derive(Debug, Eq, Clone, PartialOrd, Ord)]
+#[derive(Debug, Eq, Clone, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum LangIdentifier {
     Vhdl(VhdlIdentifier),
     Verilog(VerilogIdentifier),
@@ -491,15 +493,18 @@ impl Display for LangIdentifier {
     }
 }
 
-pub fn collect_units(files: &Vec<String>) -> Result<HashMap<LangIdentifier, LangUnit>, CodeFault> {
+pub fn collect_units(
+    files: &Vec<String>,
+    cache: Option<&SymbolCache>,
+) -> Result<HashMap<LangIdentifier, LangUnit>, CodeFault> {
     // collect the VHDL units
-    let vhdl_units = vhdl::primaryunit::collect_units(&files)?;
+    let vhdl_units = vhdl::primaryunit::collect_units(&files, cache)?;
 
     // collect the Verilog units
-    let verilog_units = verilog::primaryunit::collect_units(&files)?;
+    let verilog_units = verilog::primaryunit::collect_units(&files, cache)?;
 
     // collect the SystemVerilog units
-    let systemverilog_units = sv::primaryunit::collect_units(&files)?;
+    let systemverilog_units = sv::primaryunit::collect_units(&files, cache)?;
 
     // merge the two results into a common struct
     let mut results =
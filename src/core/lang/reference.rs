@@ -15,6 +15,7 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use serde_derive::{Deserialize, Serialize};
 use std::{collections::HashSet, fmt::Display};
 
 use super::LangIdentifier;
@@ -28,7 +29,7 @@ pub type RefSet = HashSet<CompoundIdentifier>;
 /// assume the pattern can be found anywhere.
 ///
 /// A special case is just a simple name (1 identifier) when referencing a component name.
-#[derive(Debug, Clone, Hash, PartialEq, Eq, Ord, PartialOrd)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct CompoundIdentifier {
     prefix: Option<LangIdentifier>,
     suffix: LangIdentifier,
@@ -17,8 +17,9 @@
 
 use super::{symbols::SystemVerilogSymbol, token::identifier::Identifier};
 use crate::core::lang;
+use crate::core::lang::cache::{self, SymbolCache};
 use crate::core::lang::vhdl::primaryunit::HdlNamingError;
-use crate::{core::lang::sv::symbols::SystemVerilogParser, util::anyerror::CodeFault};
+use crate::util::anyerror::CodeFault;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -141,7 +142,10 @@ impl PartialEq for Unit {
 
 impl Eq for Unit {}
 
-fn analyze(source_file: &str) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
+fn analyze(
+    source_file: &str,
+    cache: Option<&SymbolCache>,
+) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
     if crate::core::fileset::is_systemverilog(&source_file) == false {
         return Ok(HashMap::new());
     }
@@ -149,8 +153,8 @@ fn analyze(source_file: &str) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFa
     // println!("parse verilog: {:?}", source_file);
     // parse text into Verilog symbols
     let contents = lang::read_to_string(&source_file)?;
-    let symbols = match SystemVerilogParser::read(&contents) {
-        Ok(s) => s.into_symbols(),
+    let symbols = match cache::read_systemverilog(cache, &contents) {
+        Ok(s) => s,
         Err(e) => Err(CodeFault(Some(source_file.to_string()), Box::new(e)))?,
     };
     // transform into primary design units
@@ -254,12 +258,15 @@ fn analyze(source_file: &str) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFa
     Ok(units)
 }
 
-pub fn collect_units(files: &Vec<String>) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
+pub fn collect_units(
+    files: &Vec<String>,
+    cache: Option<&SymbolCache>,
+) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
     let mut all_results: HashMap<Identifier, PrimaryUnit> = HashMap::new();
     // iterate through all source files
     let divided_results: Result<Vec<_>, _> = files
         .iter()
-        .map(|source_file| analyze(source_file))
+        .map(|source_file| analyze(source_file, cache))
         .collect();
 
     for pri_unit in divided_results? {
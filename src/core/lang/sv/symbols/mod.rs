@@ -15,6 +15,7 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use serde_derive::{Deserialize, Serialize};
 use std::iter::Peekable;
 
 use checker::Checker;
@@ -62,7 +63,7 @@ fn statement_to_string(stmt: &Statement) -> String {
 }
 
 /// Design elements of the SystemVerilog Language.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum SystemVerilogSymbol {
     Module(Module),
     Config(Config),
@@ -15,6 +15,7 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use serde_derive::{Deserialize, Serialize};
 use std::iter::Peekable;
 
 use crate::core::lang::{
@@ -29,7 +30,7 @@ use crate::core::lang::{
 
 use super::SystemVerilogSymbol;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Program {
     name: Identifier,
     refs: RefSet,
@@ -15,13 +15,14 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use serde_derive::{Deserialize, Serialize};
 use std::fmt::Display;
 
 use crate::core::lang::highlight;
 use crate::core::lang::highlight::ToColor;
 use colored::ColoredString;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Keyword {
     AcceptOn,
     Alias,
@@ -791,15 +792,6 @@ impl Display for Keyword {
     }
 }
 
-impl serde::Serialize for Keyword {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_str(self.as_str())
-    }
-}
-
 impl ToColor for Keyword {
     fn to_color(&self) -> ColoredString {
         highlight::style::keyword(&self.to_string())
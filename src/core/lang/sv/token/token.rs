@@ -19,6 +19,7 @@ use crate::core::lang::highlight;
 use crate::core::lang::highlight::ToColor;
 use colored::ColoredString;
 use colored::Colorize;
+use serde_derive::{Deserialize, Serialize};
 use std::fmt::Display;
 
 use crate::core::lang::{
@@ -34,7 +35,7 @@ use crate::core::lang::{
 
 use super::{keyword::Keyword, operator::Operator};
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum SystemVerilogToken {
     Comment(Comment),
     Operator(Operator),
@@ -23,9 +23,9 @@ use super::super::sv::token::{
 };
 use crate::core::lang::highlight::ColorVec;
 use crate::core::lang::highlight::ToColor;
-use serde_derive::Serialize;
+use serde_derive::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Expr(Option<Vec<SystemVerilogToken>>);
 
 impl Expr {
@@ -43,15 +43,12 @@ impl Expr {
     }
 }
 
-impl serde::Serialize for Expr {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        match &self.0 {
-            Some(expr) => serializer.serialize_str(&tokens_to_string(&expr).into_all_bland()),
-            None => serializer.serialize_none(),
-        }
+impl Expr {
+    /// Writes the expression as plain text, if one exists.
+    pub fn to_bland_string(&self) -> Option<String> {
+        self.0
+            .as_ref()
+            .map(|expr| tokens_to_string(&expr).into_all_bland())
     }
 }
 
@@ -291,11 +288,7 @@ pub enum Direction {
     Ref,
 }
 
-fn default_mode() -> Keyword {
-    Keyword::Input
-}
-
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct DataType {
     net: Option<Keyword>,
     is_signed: bool,
@@ -408,11 +401,9 @@ impl Default for DataType {
     }
 }
 
-impl serde::Serialize for DataType {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
+impl DataType {
+    /// Writes the datatype and its range as plain text, if either exists.
+    pub fn to_bland_string(&self) -> Option<String> {
         let mut result = String::new();
         if let Some(dt) = &self.data {
             result.push_str(&dt.to_string());
@@ -421,28 +412,21 @@ impl serde::Serialize for DataType {
             result.push_str(&tokens_to_string(rg).into_all_bland());
         }
         match result.len() {
-            0 => serializer.serialize_none(),
-            _ => serializer.serialize_str(&result),
+            0 => None,
+            _ => Some(result),
         }
     }
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Port {
-    #[serde(skip_serializing)]
     is_param: bool,
-    #[serde(skip_serializing)]
     unpacked_range: Expr,
     // ANSI-style forces all things of a port to be specified in port list in one-shot
-    #[serde(skip_serializing)]
     is_ansi: bool,
-    #[serde(rename = "identifier")]
     name: Identifier,
-    #[serde(rename = "mode", default = "default_mode")]
     mode: Option<Keyword>,
-    #[serde(rename = "type", default)]
     data_type: DataType,
-    #[serde(rename = "default")]
     value: Expr,
 }
 
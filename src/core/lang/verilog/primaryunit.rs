@@ -17,8 +17,9 @@
 
 use super::{symbols::VerilogSymbol, token::identifier::Identifier};
 use crate::core::lang;
+use crate::core::lang::cache::{self, SymbolCache};
 use crate::core::lang::vhdl::primaryunit::HdlNamingError;
-use crate::{core::lang::verilog::symbols::VerilogParser, util::anyerror::CodeFault};
+use crate::util::anyerror::CodeFault;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -127,7 +128,10 @@ impl PartialEq for Unit {
 
 impl Eq for Unit {}
 
-fn analyze(source_file: &str) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
+fn analyze(
+    source_file: &str,
+    cache: Option<&SymbolCache>,
+) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
     if crate::core::fileset::is_verilog(&source_file) == false {
         return Ok(HashMap::new());
     }
@@ -135,8 +139,8 @@ fn analyze(source_file: &str) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFa
     // println!("parse verilog: {:?}", source_file);
     // parse text into Verilog symbols
     let contents = lang::read_to_string(&source_file)?;
-    let symbols = match VerilogParser::read(&contents) {
-        Ok(s) => s.into_symbols(),
+    let symbols = match cache::read_verilog(cache, &contents) {
+        Ok(s) => s,
         Err(e) => Err(CodeFault(Some(source_file.to_string()), Box::new(e)))?,
     };
 
@@ -186,12 +190,15 @@ fn analyze(source_file: &str) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFa
     Ok(units)
 }
 
-pub fn collect_units(files: &Vec<String>) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
+pub fn collect_units(
+    files: &Vec<String>,
+    cache: Option<&SymbolCache>,
+) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
     let mut all_results: HashMap<Identifier, PrimaryUnit> = HashMap::new();
     // iterate through all source files
     let divided_results: Result<Vec<_>, _> = files
         .iter()
-        .map(|source_file| analyze(source_file))
+        .map(|source_file| analyze(source_file, cache))
         .collect();
 
     for pri_unit in divided_results? {
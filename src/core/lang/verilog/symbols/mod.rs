@@ -15,6 +15,7 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use serde_derive::{Deserialize, Serialize};
 use std::iter::Peekable;
 
 use super::super::sv::token::operator::Operator;
@@ -55,7 +56,7 @@ fn statement_to_string(stmt: &Statement) -> String {
 }
 
 /// Design elements of the Verilog Language.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum VerilogSymbol {
     Module(Module),
     Config(Config),
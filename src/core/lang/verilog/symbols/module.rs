@@ -36,24 +36,19 @@ use crate::core::lang::{
 use crate::core::lang::highlight;
 use crate::core::lang::highlight::ToColor;
 
-use serde_derive::Serialize;
+use serde_derive::{Deserialize, Serialize};
 use std::iter::Peekable;
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Module {
-    #[serde(rename = "identifier")]
     name: Identifier,
-    #[serde(rename = "generics")]
     parameters: ParamList,
     ports: PortList,
     architectures: Vec<()>,
     /// The set of names that were referenced in the entity.
-    #[serde(skip_serializing)]
     refs: RefSet,
     /// The set of references that were identified as components.
-    #[serde(skip_serializing)]
     deps: RefSet,
-    #[serde(skip_serializing)]
     pos: Position,
     language: String,
 }
@@ -67,6 +62,18 @@ impl Module {
         &self.pos
     }
 
+    pub fn get_parameters(&self) -> &ParamList {
+        &self.parameters
+    }
+
+    pub fn get_ports(&self) -> &PortList {
+        &self.ports
+    }
+
+    pub fn get_language(&self) -> &str {
+        &self.language
+    }
+
     pub fn get_refs(&self) -> &RefSet {
         &self.refs
     }
@@ -22,10 +22,11 @@ use crate::core::lang::{
     sv::token::{keyword::Keyword, operator::Operator, token::SystemVerilogToken},
     verilog::{error::VerilogError, interface::PortList, token::identifier::Identifier},
 };
+use serde_derive::{Deserialize, Serialize};
 
 use std::iter::Peekable;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Primitive {
     name: Identifier,
     ports: PortList,
@@ -15,7 +15,7 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
-use serde_derive::Serialize;
+use serde_derive::{Deserialize, Serialize};
 
 use super::super::error::VerilogError;
 use super::token::VerilogToken;
@@ -30,8 +30,7 @@ use crate::core::lang::highlight;
 use crate::core::lang::highlight::ToColor;
 use colored::ColoredString;
 
-#[derive(Debug, Clone, PartialOrd, Ord, Serialize)]
-#[serde(untagged)]
+#[derive(Debug, Clone, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Identifier {
     Basic(String),
     Escaped(String),
@@ -18,11 +18,12 @@
 use crate::core::lang::highlight;
 use crate::core::lang::highlight::ToColor;
 use colored::ColoredString;
+use serde_derive::{Deserialize, Serialize};
 use std::fmt::Display;
 
 // There are 124 keywords in the Verilog-2005 LRM, with 'unsigned' reserved for future use.
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Keyword {
     Always,
     And,
@@ -15,13 +15,14 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use serde_derive::{Deserialize, Serialize};
 use std::fmt::Display;
 
 use crate::core::lang::highlight::ToColor;
 use colored::ColoredString;
 use colored::Colorize;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Operator {
     ConcatL,
     ConcatR,
@@ -32,6 +32,7 @@ use std::fmt::Display;
 use crate::core::lang::highlight;
 use crate::core::lang::highlight::ToColor;
 use colored::ColoredString;
+use serde_derive::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum VerilogToken {
@@ -660,7 +661,7 @@ impl VerilogToken {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Comment {
     OneLine(String),
     Block(String),
@@ -21,8 +21,7 @@ use super::format::VhdlFormat;
 use super::token::identifier::Identifier;
 use crate::core::lang::highlight::ToColor;
 
-use serde::ser::{Serialize, SerializeStruct, Serializer};
-use serde_derive::Serialize;
+use serde_derive::{Deserialize, Serialize};
 
 pub fn library_statement(lib: &Identifier) -> String {
     format!(
@@ -40,6 +39,10 @@ impl<'a> Architectures<'a> {
     pub fn new(archs: &'a Vec<super::symbols::architecture::Architecture>) -> Self {
         Self(archs)
     }
+
+    pub fn iter(&self) -> std::slice::Iter<'a, super::symbols::architecture::Architecture> {
+        self.0.iter()
+    }
 }
 
 impl<'a> std::fmt::Display for Architectures<'a> {
@@ -85,7 +88,7 @@ impl IdentifierList {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct SubtypeIndication(Vec<VhdlToken>);
 
 impl SubtypeIndication {
@@ -184,16 +187,14 @@ impl SubtypeIndication {
     }
 }
 
-impl Serialize for SubtypeIndication {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_str(&tokens_to_string(&self.0).into_all_bland())
+impl SubtypeIndication {
+    /// Writes the subtype indication as plain text.
+    pub fn to_bland_string(&self) -> String {
+        tokens_to_string(&self.0).into_all_bland()
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 struct StaticExpression(Vec<VhdlToken>);
 
 impl StaticExpression {
@@ -214,7 +215,7 @@ impl StaticExpression {
     }
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Generics(pub InterfaceDeclarations);
 
 impl Generics {
@@ -223,7 +224,7 @@ impl Generics {
     }
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Ports(pub InterfaceDeclarations);
 
 impl Ports {
@@ -246,7 +247,7 @@ impl std::fmt::Display for StaticExpression {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Mode(Option<Keyword>);
 
 impl Mode {
@@ -255,33 +256,26 @@ impl Mode {
     }
 }
 
-impl Serialize for Mode {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        // 3 is the number of fields in the struct.
-        let mut state = serializer.serialize_struct("Mode", 1)?;
+impl Mode {
+    /// Writes the mode's keyword in lowercase, which is `in` when no mode was
+    /// specified.
+    pub fn to_lowercase_string(&self) -> String {
         match &self.0 {
-            Some(kw) => state.serialize_field("mode", &kw.to_string().to_lowercase()),
-            None => state.serialize_field("mode", &Keyword::In.to_string().to_lowercase()),
-        }?;
-        state.end()
+            Some(kw) => kw.to_string().to_lowercase(),
+            None => Keyword::In.to_string().to_lowercase(),
+        }
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Expr(Option<StaticExpression>);
 
-impl Serialize for Expr {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        match &self.0 {
-            Some(expr) => serializer.serialize_str(&tokens_to_string(&expr.0).into_all_bland()),
-            None => serializer.serialize_none(),
-        }
+impl Expr {
+    /// Writes the expression as plain text, if one exists.
+    pub fn to_bland_string(&self) -> Option<String> {
+        self.0
+            .as_ref()
+            .map(|expr| tokens_to_string(&expr.0).into_all_bland())
     }
 }
 
@@ -294,19 +288,13 @@ impl Expr {
     }
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct InterfaceDeclaration {
-    #[serde(rename = "identifier")]
     identifier: Identifier,
-    #[serde(rename = "mode", flatten)]
     mode: Mode,
-    #[serde(rename = "type")]
     datatype: SubtypeIndication,
-    #[serde(rename = "default")]
     expr: Expr,
-    #[serde(skip_serializing)]
     initial_keyword: Option<Keyword>,
-    #[serde(skip_serializing)]
     bus_present: bool,
 }
 
@@ -483,7 +471,7 @@ impl InterfaceDeclaration {
     }
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct InterfaceDeclarations(pub Vec<InterfaceDeclaration>);
 
 impl InterfaceDeclarations {
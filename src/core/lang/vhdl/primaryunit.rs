@@ -19,8 +19,8 @@ use super::super::lexer::Position;
 use super::subunit::SubUnit;
 use super::symbols::VhdlSymbol;
 use crate::core::lang;
+use crate::core::lang::cache::{self, SymbolCache};
 use crate::core::lang::reference::RefSet;
-use crate::core::lang::vhdl::symbols::VHDLParser;
 use crate::core::lang::vhdl::token::identifier::Identifier;
 use crate::util::anyerror::CodeFault;
 use crate::util::filesystem;
@@ -156,15 +156,18 @@ impl Eq for Unit {}
 
 // use rayon::prelude::*;
 
-fn analyze(source_file: &str) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
+fn analyze(
+    source_file: &str,
+    cache: Option<&SymbolCache>,
+) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
     if crate::core::fileset::is_vhdl(&source_file) == false {
         return Ok(HashMap::new());
     }
     // parse text into VHDL symbols
     // println!("parsing vhdl: {}", &source_file);
     let contents = lang::read_to_string(&source_file)?;
-    let symbols = match VHDLParser::read(&contents) {
-        Ok(s) => s.into_symbols(),
+    let symbols = match cache::read_vhdl(cache, &contents) {
+        Ok(s) => s,
         Err(e) => Err(CodeFault(Some(source_file.to_string()), Box::new(e)))?,
     };
 
@@ -223,12 +226,15 @@ fn analyze(source_file: &str) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFa
     Ok(pri_units)
 }
 
-pub fn collect_units(files: &Vec<String>) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
+pub fn collect_units(
+    files: &Vec<String>,
+    cache: Option<&SymbolCache>,
+) -> Result<HashMap<Identifier, PrimaryUnit>, CodeFault> {
     let mut all_result: HashMap<Identifier, PrimaryUnit> = HashMap::new();
     // iterate through all source files
     let divided_results: Result<Vec<_>, _> = files
         .iter()
-        .map(|source_file| analyze(source_file))
+        .map(|source_file| analyze(source_file, cache))
         .collect();
 
     for pri_unit in divided_results? {
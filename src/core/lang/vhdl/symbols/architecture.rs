@@ -17,19 +17,14 @@
 
 use super::{Identifier, Position};
 use crate::core::lang::reference::RefSet;
-use serde_derive::Serialize;
+use serde_derive::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Clone, Serialize)]
-#[serde(transparent)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Architecture {
     name: Identifier,
-    #[serde(skip_serializing)]
     owner: Identifier,
-    #[serde(skip_serializing)]
     refs: RefSet,
-    #[serde(skip_serializing)]
     deps: RefSet,
-    #[serde(skip_serializing)]
     pos: Position,
 }
 
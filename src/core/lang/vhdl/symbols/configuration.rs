@@ -16,10 +16,11 @@
 //
 
 use crate::core::lang::reference::RefSet;
+use serde_derive::{Deserialize, Serialize};
 
 use super::{Identifier, Position};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Configuration {
     name: Identifier,
     owner: Identifier,
@@ -17,7 +17,7 @@
 
 use std::iter::Peekable;
 
-use serde_derive::Serialize;
+use serde_derive::{Deserialize, Serialize};
 
 use crate::core::lang::{
     reference::RefSet,
@@ -32,20 +32,16 @@ use super::{
     InterfaceDeclarations, Keyword, Ports, Position, Token, VhdlSymbol, VhdlToken,
 };
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Entity {
-    #[serde(rename = "identifier")]
     name: Identifier,
     generics: Generics,
     ports: Ports,
     architectures: Vec<Architecture>,
     /// The set of names that were referenced in the entity.
-    #[serde(skip_serializing)]
     refs: RefSet,
     /// The set of references that were identified as components.
-    #[serde(skip_serializing)]
     deps: RefSet,
-    #[serde(skip_serializing)]
     pos: Position,
     language: String,
 }
@@ -15,6 +15,7 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use serde_derive::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fmt::Display;
 
@@ -40,7 +41,7 @@ pub mod entity;
 pub mod package;
 pub mod packagebody;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum VhdlSymbol {
     // primary design units
     Entity(Entity),
@@ -208,7 +209,7 @@ impl Display for VhdlSymbol {
 
 use super::error::VhdlError;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Context {
     name: Identifier,
     refs: RefSet,
@@ -15,13 +15,14 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use serde_derive::{Deserialize, Serialize};
 use std::fmt::Display;
 
 use crate::core::lang::reference::RefSet;
 
 use super::{packagebody::PackageBody, Generics, Identifier, Position};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Package {
     name: Identifier,
     generics: Generics,
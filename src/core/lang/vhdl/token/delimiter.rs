@@ -18,9 +18,10 @@
 use crate::core::lang::highlight::ToColor;
 use colored::ColoredString;
 use colored::Colorize;
+use serde_derive::{Deserialize, Serialize};
 use std::fmt::Display;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Delimiter {
     Ampersand,   // &
     SingleQuote, // '
@@ -20,6 +20,7 @@ use super::super::token::char_set;
 use crate::core::lang::highlight;
 use crate::core::lang::highlight::ToColor;
 use colored::ColoredString;
+use serde_derive::{Deserialize, Serialize};
 use std::fmt::Display;
 use std::str::FromStr;
 
@@ -40,7 +41,7 @@ pub fn interpret_integer(s: &str) -> usize {
         .expect("integer can only contain 0..=9 or underline '_'")
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Character(pub String);
 
 impl Display for Character {
@@ -49,7 +50,7 @@ impl Display for Character {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct BitStrLiteral(pub String);
 
 impl Display for BitStrLiteral {
@@ -58,7 +59,7 @@ impl Display for BitStrLiteral {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum AbstLiteral {
     Decimal(String),
     Based(String),
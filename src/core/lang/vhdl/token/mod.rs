@@ -18,6 +18,7 @@
 use super::super::lexer::TrainCar;
 use colored::ColoredString;
 use colored::Colorize;
+use serde_derive::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::str::FromStr;
@@ -42,7 +43,7 @@ pub type VhdlTokenizer = tokenizer::VhdlTokenizer;
 pub type VhdlError = super::error::VhdlError;
 use crate::core::lang::highlight::ToColor;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum VhdlToken {
     Comment(Comment),             // (String)
     Identifier(Identifier), // (String) ...can be general or extended (case-sensitive) identifier
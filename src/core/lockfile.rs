@@ -103,7 +103,7 @@ impl FromFile for LockFile {
 
 // version 1 for the lockfile
 pub mod v1 {
-    use version::PartialVersion;
+    use version::VersionReq;
 
     use crate::core::{
         catalog::{Catalog, DownloadSlot},
@@ -185,10 +185,10 @@ pub mod v1 {
         }
 
         /// Returns an exact match of `target` and `version` from within the lockfile.
-        pub fn get(&self, target: &PkgPart, version: &PartialVersion) -> Option<&LockEntry> {
+        pub fn get(&self, target: &PkgPart, version: &VersionReq) -> Option<&LockEntry> {
             self.ip
                 .iter()
-                .find(|&f| &f.name == target && version::is_compatible(version, &f.version))
+                .find(|&f| &f.name == target && version.matches(&f.version))
         }
 
         /// Returns the current working ip, denoted by not having a checksum with it.
@@ -252,7 +252,7 @@ pub mod v1 {
                         // check if this entry is a dev dependency
                         |p| match target.get_man().get_dev_deps().get(p.get_name()) {
                             Some(v) => {
-                                if version::is_compatible(v.get_version(), p.get_version()) {
+                                if v.get_version().matches(p.get_version()) {
                                     false
                                 } else {
                                     true
@@ -20,12 +20,13 @@
 use crate::core::ip::IpSpec;
 use crate::core::lang::vhdl::token::Identifier;
 use crate::core::pkgid::PkgPart;
+use crate::core::source;
 use crate::core::source::Source;
-use crate::core::{source, version};
 use crate::error::Error;
 use crate::util::anyerror::{AnyError, Fault};
 use serde::de::{self, MapAccess, Visitor};
 use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt::{self, Display};
 use std::path::PathBuf;
 use std::{collections::HashMap, str::FromStr};
@@ -37,7 +38,7 @@ use super::uuid::Uuid;
 
 pub type IpName = PkgPart;
 pub type IpVersion = crate::core::version::Version;
-pub type DepVersion = crate::core::version::PartialVersion;
+pub type DepVersion = crate::core::version::VersionReq;
 
 #[derive(Serialize, Debug, PartialEq)]
 #[serde(deny_unknown_fields, transparent)]
@@ -215,6 +216,8 @@ pub struct Manifest {
         default
     )]
     dev_dependencies: Dependencies,
+    #[serde(skip_serializing_if = "vec_is_empty", default)]
+    attributes: Vec<Attributes>,
 }
 
 pub trait FromFile: FromStr
@@ -265,7 +268,7 @@ impl FromFile for Manifest {
                     )?;
                     // verify the ip loaded has the correct version assigned by the user
                     let ip_version = ip.get_man().get_ip().get_version();
-                    if version::is_compatible(dep.get_version(), ip_version) == false {
+                    if dep.get_version().matches(ip_version) == false {
                         return Err(Error::DependencyIpRelativeBadVersion(
                             dep.get_version().clone(),
                             ip_version.clone(),
@@ -284,7 +287,7 @@ impl FromFile for Manifest {
                 }
             }
             // verify there are no cycles in the ip dependency graph
-            if name == &local_name && version::is_compatible(dep.get_version(), &local_version) {
+            if name == &local_name && dep.get_version().matches(&local_version) {
                 return Err(Error::CyclicDependencyIp(local_name))?;
             }
         }
@@ -322,6 +325,7 @@ impl Manifest {
             },
             dependencies: Dependencies::new(),
             dev_dependencies: Dependencies::new(),
+            attributes: Vec::new(),
         }
     }
 
@@ -398,6 +402,11 @@ library = "{}"
         &self.dev_dependencies
     }
 
+    /// Returns the list of entries found under the "attributes" table array.
+    pub fn get_attributes(&self) -> &Vec<Attributes> {
+        &self.attributes
+    }
+
     pub fn is_deps_valid(&self) -> Result<(), AnyError> {
         for (key, _) in &self.dependencies {
             if let Some(_) = self.dev_dependencies.get(key) {
@@ -562,6 +571,64 @@ impl Package {
     }
 }
 
+/// A set of attributes assigned to every file matching the glob-style patterns
+/// of `files`.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct Attributes {
+    /// Filepaths that receive the attributes
+    files: Vec<String>,
+    #[serde(flatten)]
+    values: FileAttributes,
+}
+
+impl Attributes {
+    pub fn get_files(&self) -> &Vec<String> {
+        &self.files
+    }
+
+    pub fn get_values(&self) -> &FileAttributes {
+        &self.values
+    }
+}
+
+/// The attributes that describe how to process a single file.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
+pub struct FileAttributes {
+    /// The language standard (revision) the file is written in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    std: Option<String>,
+    /// Macros or constants to define when processing the file
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    defines: BTreeMap<String, toml::Value>,
+    /// Any other user-defined key-value pairs
+    #[serde(flatten)]
+    other: BTreeMap<String, toml::Value>,
+}
+
+impl FileAttributes {
+    pub fn get_std(&self) -> &Option<String> {
+        &self.std
+    }
+
+    pub fn get_defines(&self) -> &BTreeMap<String, toml::Value> {
+        &self.defines
+    }
+
+    /// Applies the values from `rhs` on top of the existing values.
+    ///
+    /// Any key set by both takes the value from `rhs`, while the defines are
+    /// combined together.
+    pub fn merge(&mut self, rhs: &FileAttributes) {
+        if let Some(std) = &rhs.std {
+            self.std = Some(std.clone());
+        }
+        self.defines
+            .extend(rhs.defines.iter().map(|(k, v)| (k.clone(), v.clone())));
+        self.other
+            .extend(rhs.other.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+}
+
 /// Takes an iterative approach to iterating through directories to find a file
 /// matching `name`.
 ///
@@ -687,6 +754,34 @@ mod test {
             assert_eq!(man.ip.library, Some(PkgPart::from_str("common").unwrap()));
         }
 
+        #[test]
+        fn ut_version_req() {
+            let man: Manifest = toml::from_str(&format!(
+                "{}\n[dependencies]\ngates = \">=1.4.2, <2, !=1.5.0\"\nalu = \"1.2\"\n",
+                EX2
+            ))
+            .unwrap();
+            let ver = |s: &str| IpVersion::from_str(s).unwrap();
+            let gates = man
+                .get_deps()
+                .get(&PkgPart::from_str("gates").unwrap())
+                .unwrap();
+            assert_eq!(gates.get_version().matches(&ver("1.9.0")), true);
+            assert_eq!(gates.get_version().matches(&ver("1.5.0")), false);
+            assert_eq!(gates.get_version().matches(&ver("2.0.0")), false);
+            // a partial version keeps its meaning
+            let alu = man
+                .get_deps()
+                .get(&PkgPart::from_str("alu").unwrap())
+                .unwrap();
+            assert_eq!(alu.get_version().matches(&ver("1.2.7")), true);
+            assert_eq!(alu.get_version().matches(&ver("1.3.0")), false);
+            assert_eq!(
+                man.to_string().contains("gates = \">=1.4.2, <2, !=1.5.0\""),
+                true
+            );
+        }
+
         #[test]
         fn ut_bad() {
             let man = toml::from_str::<Manifest>(ERR1);
@@ -702,6 +797,34 @@ mod test {
             assert_eq!(text, EX3);
         }
 
+        #[test]
+        fn ut_attributes() {
+            let man: Manifest = toml::from_str(EX8).unwrap();
+
+            let attrs = man.get_attributes();
+            assert_eq!(attrs.len(), 2);
+            assert_eq!(attrs[0].get_files(), &vec![String::from("*.vhd")]);
+            assert_eq!(attrs[0].get_values().get_std(), &Some(String::from("2008")));
+            assert_eq!(attrs[1].get_values().get_std(), &None);
+
+            let mut values = attrs[0].get_values().clone();
+            values.merge(attrs[1].get_values());
+            assert_eq!(values.get_std(), &Some(String::from("2008")));
+            assert_eq!(values.get_defines().len(), 2);
+            assert_eq!(
+                values.get_defines().get("WIDTH"),
+                Some(&toml::Value::Integer(16))
+            );
+            assert_eq!(
+                values.other.get("synthesize"),
+                Some(&toml::Value::Boolean(false))
+            );
+
+            // attributes survive writing the manifest back out
+            let text = man.to_string();
+            assert_eq!(toml::from_str::<Manifest>(&text).unwrap(), man);
+        }
+
         #[test]
         fn ut_complex_source() {
             let man: Manifest = match toml::from_str(EX4) {
@@ -851,5 +974,21 @@ version = "1.20.0"
 source = { protocol = "ktsp" }
 "#;
 
+const EX8: &str = r#"[ip]
+name = "lab2"
+uuid = "0000000000000000000000000"
+version = "1.20.0"
+
+[[attributes]]
+files = ["*.vhd"]
+std = "2008"
+defines = { WIDTH = 8, DEBUG = true }
+
+[[attributes]]
+files = ["/sim"]
+defines = { WIDTH = 16 }
+synthesize = false
+"#;
+
 const ERR1: &str = r#"[ip]
 "#;
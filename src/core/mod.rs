@@ -16,12 +16,16 @@
 //
 
 pub mod algo;
+pub mod artifacts;
 pub mod blueprint;
 pub mod catalog;
 pub mod channel;
 pub mod config;
 pub mod context;
+pub mod explain;
 pub mod fileset;
+pub mod fingerprint;
+pub mod generic;
 pub mod ip;
 pub mod iparchive;
 pub mod ippointer;
@@ -36,3 +40,4 @@ pub mod target;
 pub mod uuid;
 pub mod version;
 pub mod visibility;
+pub mod watch;
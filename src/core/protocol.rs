@@ -33,6 +33,8 @@ pub struct Protocol {
     description: Option<String>,
     command: String,
     args: Option<Vec<String>>,
+    timeout: Option<u64>,
+    retries: Option<u32>,
     #[serde(skip_serializing, skip_deserializing)]
     root: Option<PathBuf>,
 }
@@ -66,8 +68,8 @@ impl Process for Protocol {
         &self.root.as_ref().unwrap()
     }
 
-    fn get_command(&self) -> &String {
-        &self.command
+    fn get_command(&self) -> Result<&String, crate::error::Error> {
+        Ok(&self.command)
     }
 
     fn get_args(&self) -> Vec<&String> {
@@ -76,6 +78,14 @@ impl Process for Protocol {
             None => Vec::new(),
         }
     }
+
+    fn get_timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+
+    fn get_retries(&self) -> u32 {
+        self.retries.unwrap_or(0)
+    }
 }
 
 use crate::commands::orbit::UpgradeError;
@@ -96,6 +106,8 @@ impl Protocol {
             root: None,
             args: None,
             description: None,
+            timeout: None,
+            retries: None,
         }
     }
 
@@ -235,6 +247,8 @@ args = ["~/scripts/download.bash"]
                 args: None,
                 root: None,
                 description: None,
+                timeout: None,
+                retries: None,
             }
         );
 
@@ -247,6 +261,8 @@ args = ["~/scripts/download.bash"]
                 args: Some(vec![String::from("~/scripts/download.bash")]),
                 root: None,
                 description: None,
+                timeout: None,
+                retries: None,
             }
         );
     }
@@ -17,14 +17,24 @@
 
 use crate::core::context::Context;
 use crate::core::fileset::Style;
+use crate::core::lang::Lang;
 use crate::error::Error;
-use crate::util::anyerror::Fault;
+use crate::error::LastError;
+use crate::util::anyerror::{AnyError, Fault};
+use crate::util::environment;
 use crate::util::filesystem;
+use crate::util::logfile::{LogFile, Timestamp};
+use crate::util::strcmp;
+use crate::util::subprocess::{self, Outcome};
 use colored::Colorize;
+use regex::Regex;
+use serde::de;
+use serde::Serializer;
 use serde_derive::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 use super::blueprint::Scheme;
 use super::swap;
@@ -32,7 +42,11 @@ use super::swap::StrSwapTable;
 
 pub type Targets = Vec<Target>;
 
-type Filesets = HashMap<String, Style>;
+/// The file within the target's output directory that stores the script to
+/// reproduce a run.
+pub const SCRIPT_FILE: &str = "reproduce.sh";
+
+type Filesets = BTreeMap<String, Style>;
 
 /// A user-defined backend workflow for processing the files collected
 /// in the generated blueprint file.
@@ -43,14 +57,32 @@ pub struct Target {
     description: Option<String>,
     #[serde(skip_serializing, skip_deserializing)]
     root: Option<PathBuf>,
-    command: String,
+    extends: Option<String>,
+    command: Option<String>,
     args: Option<Vec<String>>,
+    steps: Option<Vec<Step>>,
     fileset: Option<Filesets>,
     plans: Option<Vec<Scheme>>,
+    #[serde(rename = "rtl-only")]
+    rtl_only: Option<bool>,
+    env: Option<BTreeMap<String, String>>,
+    #[serde(rename = "isolate-env")]
+    isolate_env: Option<bool>,
+    #[serde(rename = "keep-env")]
+    keep_env: Option<Vec<String>>,
+    artifacts: Option<Vec<String>>,
+    timeout: Option<u64>,
+    retries: Option<u32>,
+    #[serde(rename = "pass-pattern")]
+    pass_pattern: Option<String>,
+    #[serde(rename = "fail-pattern")]
+    fail_pattern: Option<String>,
+    languages: Option<Vec<LangSupport>>,
 }
 
 impl Target {
-    /// Performs variable substitution on the provided arguments for the target.
+    /// Performs variable substitution on the provided arguments and environment
+    /// variables for the target.
     pub fn replace_vars_in_args(mut self, vtable: &StrSwapTable) -> Self {
         self.args = if let Some(args) = self.args {
             Some(
@@ -61,9 +93,479 @@ impl Target {
         } else {
             self.args
         };
+        self.env = self.env.map(|vars| {
+            vars.into_iter()
+                .map(|(k, v)| (k, swap::substitute(v, vtable)))
+                .collect()
+        });
+        self.steps = self
+            .steps
+            .map(|steps| steps.into_iter().map(|s| s.replace_vars(vtable)).collect());
+        self
+    }
+
+    /// Inherits the definition of the `parent` target for every field this
+    /// target does not set.
+    ///
+    /// The filesets are combined, where this target's patterns take precedence
+    /// over patterns with the same name. The arguments are appended to the
+    /// parent's arguments only when the command is also inherited; a target that
+    /// sets its own command starts with only its own arguments.
+    pub fn extend(mut self, parent: &Target) -> Self {
+        // resolve the parent's paths now because the parent may be defined in a
+        // different configuration file than this target
+        let parent_root = parent.root.clone().unwrap_or_default();
+        let parent_args = parent
+            .get_args()
+            .into_iter()
+            .map(|a| filesystem::resolve_rel_path(&parent_root, a));
+        // a target that defines its own command or steps replaces the parent's process
+        let inherit_process = self.command.is_none() && self.steps.is_none();
+        self.args = match inherit_process {
+            false => self.args,
+            true => match (parent.args.is_some(), self.args) {
+                (false, args) => args,
+                (true, args) => Some(parent_args.chain(args.unwrap_or_default()).collect()),
+            },
+        };
+        if inherit_process == true {
+            self.command = parent
+                .command
+                .as_ref()
+                .map(|c| filesystem::resolve_rel_path(&parent_root, c));
+            self.steps = parent.steps.clone();
+        }
+        if self.description.is_none() == true {
+            self.description = parent.description.clone();
+        }
+        self.fileset = match (&parent.fileset, self.fileset) {
+            (Some(base), Some(fsets)) => {
+                let mut merged = base.clone();
+                merged.extend(fsets);
+                Some(merged)
+            }
+            (base, fsets) => fsets.or(base.clone()),
+        };
+        if self.plans.is_none() == true {
+            self.plans = parent.plans.clone();
+        }
+        if self.rtl_only.is_none() == true {
+            self.rtl_only = parent.rtl_only;
+        }
+        self.env = match (&parent.env, self.env) {
+            (Some(base), Some(vars)) => {
+                let mut merged = base.clone();
+                merged.extend(vars);
+                Some(merged)
+            }
+            (base, vars) => vars.or(base.clone()),
+        };
+        if self.isolate_env.is_none() == true {
+            self.isolate_env = parent.isolate_env;
+        }
+        self.keep_env = match (&parent.keep_env, self.keep_env) {
+            (Some(base), Some(keep)) => Some(base.iter().cloned().chain(keep).collect()),
+            (base, keep) => keep.or(base.clone()),
+        };
+        self.artifacts = match (&parent.artifacts, self.artifacts) {
+            (Some(base), Some(globs)) => Some(base.iter().cloned().chain(globs).collect()),
+            (base, globs) => globs.or(base.clone()),
+        };
+        if self.timeout.is_none() == true {
+            self.timeout = parent.timeout;
+        }
+        if self.retries.is_none() == true {
+            self.retries = parent.retries;
+        }
+        if self.pass_pattern.is_none() == true {
+            self.pass_pattern = parent.pass_pattern.clone();
+        }
+        if self.fail_pattern.is_none() == true {
+            self.fail_pattern = parent.fail_pattern.clone();
+        }
+        if self.languages.is_none() == true {
+            self.languages = parent.languages.clone();
+        }
+        self.extends = None;
         self
     }
 
+    /// References the name of the target this target extends.
+    pub fn get_extends(&self) -> Option<&String> {
+        self.extends.as_ref()
+    }
+
+    /// Verifies the target defines exactly one process to run: either a
+    /// command or a list of uniquely named steps.
+    pub fn validate(&self) -> Result<(), Error> {
+        match &self.steps {
+            Some(steps) => {
+                if self.command.is_some() || self.args.is_some() {
+                    return Err(Error::TargetCommandAndSteps(self.name.clone()));
+                }
+                for (i, step) in steps.iter().enumerate() {
+                    if steps[..i].iter().any(|s| s.name == step.name) == true {
+                        return Err(Error::TargetStepDuplicate(
+                            self.name.clone(),
+                            step.name.clone(),
+                        ));
+                    }
+                }
+                Ok(())
+            }
+            None => match self.command.is_some() {
+                true => Ok(()),
+                false => Err(Error::TargetMissingCommand(self.name.clone())),
+            },
+        }
+    }
+
+    /// Finds the position of the step called `name`.
+    fn find_step(&self, steps: &[Step], name: &str) -> Result<usize, Error> {
+        match steps.iter().position(|s| s.name == name) {
+            Some(i) => Ok(i),
+            None => Err(Error::TargetStepNotFound(
+                self.name.clone(),
+                name.to_string(),
+                steps
+                    .iter()
+                    .map(|s| s.name.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", "),
+            )),
+        }
+    }
+
+    /// Determines the range of steps to run, starting at the step named `from`
+    /// and ending at the step named `to`.
+    ///
+    /// Returns `None` if the target runs a command instead of steps.
+    pub fn select_steps(
+        &self,
+        from: &Option<String>,
+        to: &Option<String>,
+    ) -> Result<Option<(usize, usize)>, Error> {
+        let steps = match &self.steps {
+            Some(s) => s,
+            None => match from.is_some() || to.is_some() {
+                true => return Err(Error::TargetHasNoSteps(self.name.clone())),
+                false => return Ok(None),
+            },
+        };
+        let start = match from {
+            Some(name) => self.find_step(steps, name)?,
+            None => 0,
+        };
+        let end = match to {
+            Some(name) => self.find_step(steps, name)? + 1,
+            None => steps.len(),
+        };
+        if start >= end && steps.is_empty() == false {
+            return Err(Error::TargetStepRangeInvalid(
+                steps[start].name.clone(),
+                steps[end - 1].name.clone(),
+            ));
+        }
+        Ok(Some((start, end)))
+    }
+
+    /// Runs the target's process from the directory `cwd`.
+    ///
+    /// A target with steps runs each step in order, starting at the step named
+    /// `from` and ending at the step named `to`, and stops at the first step
+    /// that fails. Otherwise, the target's command is ran.
+    ///
+    /// The output of each process is also recorded in the `log` when given.
+    pub fn run(
+        &self,
+        overloaded_command: &Option<String>,
+        extra_args: &[String],
+        verbose: bool,
+        cwd: &PathBuf,
+        envs: HashMap<&String, &String>,
+        from: &Option<String>,
+        to: &Option<String>,
+        log: Option<&LogFile>,
+    ) -> Result<(), Fault> {
+        // assemble the environment, where the target's variables take precedence
+        let isolate = self.isolate_env.unwrap_or(false);
+        let inherited = match isolate {
+            true => environment::read_allowed_vars(self.keep_env.as_deref().unwrap_or(&[])),
+            false => Vec::new(),
+        };
+        let mut envs: HashMap<&String, &String> = inherited
+            .iter()
+            .map(|(k, v)| (k, v))
+            .chain(envs.into_iter())
+            .collect();
+        if let Some(vars) = &self.env {
+            envs.extend(vars.iter());
+        }
+
+        let (start, end) = match self.select_steps(from, to)? {
+            Some(range) => range,
+            None => {
+                return self.execute(
+                    overloaded_command,
+                    extra_args,
+                    verbose,
+                    cwd,
+                    envs,
+                    isolate,
+                    log,
+                )
+            }
+        };
+        if overloaded_command.is_some() || extra_args.is_empty() == false {
+            return Err(Error::TargetStepsNoOverride(self.name.clone()))?;
+        }
+        let steps = self.steps.as_ref().unwrap();
+        for (i, step) in steps.iter().enumerate().take(end).skip(start) {
+            // a quiet log records the step instead of displaying it
+            if log.map(|l| l.is_quiet()) != Some(true) {
+                crate::info!(
+                    "running step {} ({} of {})",
+                    step.name.green(),
+                    i + 1,
+                    steps.len()
+                );
+            }
+            let dir = match &step.dir {
+                Some(d) => cwd.join(d),
+                None => cwd.clone(),
+            };
+            std::fs::create_dir_all(&dir)?;
+            let step = step.clone().inherit_limits(self.timeout, self.retries);
+            if let Some(log) = log {
+                log.write_note(&format!(
+                    "# step: {} ({} of {})",
+                    step.name,
+                    i + 1,
+                    steps.len()
+                ))?;
+            }
+            if let Err(e) = step.execute(&None, &[], verbose, &dir, envs.clone(), isolate, log) {
+                return Err(Error::TargetStepFailed(
+                    step.name.clone(),
+                    i + 1,
+                    steps.len(),
+                    LastError(e.to_string()),
+                ))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Composes a shell script that reproduces running the target from the
+    /// directory `cwd` without orbit.
+    ///
+    /// The script exports orbit's variables, the variables in `envs`, and the
+    /// target's own variables, and then runs the same processes as
+    /// [Target::run] from the same directories. The processes' timeouts and
+    /// retries are not reproduced.
+    pub fn to_script(
+        &self,
+        overloaded_command: &Option<String>,
+        extra_args: &[String],
+        cwd: &PathBuf,
+        envs: HashMap<&String, &String>,
+        from: &Option<String>,
+        to: &Option<String>,
+    ) -> Result<String, Error> {
+        // variables set later take precedence, like when running the target
+        let mut vars: BTreeMap<String, String> = std::env::vars()
+            .filter(|(k, _)| k.starts_with(environment::ORBIT_PREFIX) == true)
+            .collect();
+        vars.extend(envs.into_iter().map(|(k, v)| (k.clone(), v.clone())));
+        if let Some(env) = &self.env {
+            vars.extend(env.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+
+        // collect the processes along with the directories they run from
+        let mut runs = Vec::new();
+        match self.select_steps(from, to)? {
+            Some((start, end)) => {
+                if overloaded_command.is_some() || extra_args.is_empty() == false {
+                    return Err(Error::TargetStepsNoOverride(self.name.clone()));
+                }
+                let steps = self.steps.as_ref().unwrap();
+                for (i, step) in steps.iter().enumerate().take(end).skip(start) {
+                    let dir = match &step.dir {
+                        Some(d) => cwd.join(d),
+                        None => cwd.clone(),
+                    };
+                    let (command, args) = step.resolve_invocation(&None, &[])?;
+                    let note = format!("step: {} ({} of {})", step.name, i + 1, steps.len());
+                    runs.push((note, dir, command, args));
+                }
+            }
+            None => {
+                let (command, args) = self.resolve_invocation(overloaded_command, extra_args)?;
+                runs.push((String::from("command"), cwd.clone(), command, args));
+            }
+        }
+
+        let isolate = self.isolate_env.unwrap_or(false);
+
+        let mut script = String::from("#!/bin/sh\n");
+        script += &format!(
+            "# Reproduces running the target {:?} without orbit.\n",
+            self.name
+        );
+        script += &format!(
+            "# Generated by orbit {} at {}.\n",
+            env!("CARGO_PKG_VERSION"),
+            Timestamp::now().to_iso()
+        );
+        script += "set -e\n\n";
+        for (k, v) in &vars {
+            script += &format!("export {}={}\n", k, shell_quote(v));
+        }
+        // an isolated process only receives the variables it would inherit
+        if isolate == true {
+            let names: BTreeSet<&str> = environment::ISOLATED_ENV_ALLOWLIST
+                .iter()
+                .map(|k| *k)
+                .chain(self.keep_env.iter().flatten().map(|k| k.as_str()))
+                .chain(vars.keys().map(|k| k.as_str()))
+                .collect();
+            script += "\nisolate() {\n    env -i \\\n";
+            for k in names {
+                script += &format!("        ${{{0}+\"{0}=${0}\"}} \\\n", k);
+            }
+            script += "        \"$@\"\n}\n";
+        }
+        for (note, dir, command, args) in runs {
+            script += &format!("\n# {}\n", note);
+            if &dir != cwd {
+                script += &format!("mkdir -p {}\n", shell_quote(&dir.to_string_lossy()));
+            }
+            script += &format!("cd {}\n", shell_quote(&dir.to_string_lossy()));
+            script += &format!(
+                "{}{}\n",
+                match isolate {
+                    true => "isolate ",
+                    false => "",
+                },
+                std::iter::once(&command)
+                    .chain(args.iter())
+                    .map(|a| shell_quote(a))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            );
+        }
+        Ok(script)
+    }
+
+    /// Writes the script that reproduces running the target into the directory
+    /// `cwd` and returns the path to the script.
+    ///
+    /// See [Target::to_script] for the script's contents.
+    pub fn write_script(
+        &self,
+        overloaded_command: &Option<String>,
+        extra_args: &[String],
+        cwd: &PathBuf,
+        envs: HashMap<&String, &String>,
+        from: &Option<String>,
+        to: &Option<String>,
+    ) -> Result<PathBuf, Fault> {
+        let script = self.to_script(overloaded_command, extra_args, cwd, envs, from, to)?;
+        let path = cwd.join(SCRIPT_FILE);
+        std::fs::write(&path, script)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+        }
+        Ok(path)
+    }
+
+    /// Checks if the target only accepts the hdl files classified as rtl.
+    pub fn is_rtl_only(&self) -> bool {
+        self.rtl_only.unwrap_or(false)
+    }
+
+    /// References the glob-style patterns of the files to collect as artifacts
+    /// after a successful run.
+    pub fn get_artifacts(&self) -> Option<&Vec<String>> {
+        self.artifacts.as_ref()
+    }
+
+    /// Compiles the regular expressions used to determine if a run passed or
+    /// failed from its output.
+    fn compile_patterns(&self) -> Result<(Option<Regex>, Option<Regex>), Error> {
+        let compile = |kind: &str, pattern: &Option<String>| match pattern {
+            Some(p) => match Regex::new(p) {
+                Ok(r) => Ok(Some(r)),
+                Err(e) => Err(Error::TargetPatternInvalid(
+                    self.name.clone(),
+                    kind.to_string(),
+                    LastError(e.to_string()),
+                )),
+            },
+            None => Ok(None),
+        };
+        Ok((
+            compile("pass", &self.pass_pattern)?,
+            compile("fail", &self.fail_pattern)?,
+        ))
+    }
+
+    /// Checks that the target's pass and fail patterns are valid regular
+    /// expressions.
+    pub fn verify_patterns(&self) -> Result<(), Error> {
+        self.compile_patterns()?;
+        Ok(())
+    }
+
+    /// Creates a check of a run's output against the target's patterns.
+    ///
+    /// Returns `None` when the target does not declare any patterns.
+    pub fn output_check(&self) -> Result<Option<OutputCheck>, Error> {
+        let (pass, fail) = self.compile_patterns()?;
+        if pass.is_none() && fail.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(OutputCheck {
+            pass: pass,
+            fail: fail,
+            passed: false,
+            failed: None,
+        }))
+    }
+
+    /// Determines if the `output` of a run that exited successfully passes
+    /// according to the target's patterns.
+    ///
+    /// The run fails if any line matches the fail pattern, or if no line matches
+    /// the pass pattern.
+    pub fn check_output(&self, output: &str) -> Result<(), Error> {
+        match self.output_check()? {
+            Some(mut check) => {
+                output.lines().for_each(|l| check.inspect(l));
+                check.verdict()
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// References the hdl languages the target supports, if it lists them.
+    pub fn get_languages(&self) -> Option<&Vec<LangSupport>> {
+        self.languages.as_ref()
+    }
+
+    /// Checks if the target can process a file written in the `lang` with the
+    /// language standard `std`.
+    ///
+    /// Every file is supported when the target does not list its languages.
+    pub fn supports_language(&self, lang: &Lang, std: &Option<String>) -> bool {
+        match &self.languages {
+            Some(langs) => langs.iter().any(|l| l.accepts(lang, std)),
+            None => true,
+        }
+    }
+
     pub fn get_filesets(&self) -> Option<&Filesets> {
         self.fileset.as_ref()
     }
@@ -134,11 +636,14 @@ impl Target {
 
     /// Sets the root directory from where the command should reference paths from.
     pub fn root(mut self, root: PathBuf) -> Self {
-        self.root = Some(root);
+        self.set_root(root);
         self
     }
 
     pub fn set_root(&mut self, root: PathBuf) {
+        if let Some(steps) = &mut self.steps {
+            steps.iter_mut().for_each(|s| s.root = root.clone());
+        }
         self.root = Some(root);
     }
 
@@ -146,11 +651,23 @@ impl Target {
     pub fn get_name(&self) -> &str {
         &self.name
     }
+
+    /// Returns the name of the output directory for running the target with
+    /// only the testbench `bench`.
+    ///
+    /// The directory sits beside the target's own output directory so that
+    /// cleaning one never removes the outputs of the other.
+    pub fn get_bench_out_dir(&self, bench: &str) -> String {
+        format!("{}-{}", self.name, bench)
+    }
 }
 
 impl std::fmt::Display for Target {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let command = filesystem::resolve_rel_path(self.get_root(), &self.command);
+        let command = self
+            .command
+            .as_ref()
+            .map(|c| filesystem::resolve_rel_path(self.get_root(), c));
 
         let args = if self.args.is_some() {
             Some(
@@ -166,17 +683,251 @@ impl std::fmt::Display for Target {
         let refreshed_target = Self {
             command: command,
             args: args,
+            extends: self.extends.clone(),
+            steps: self
+                .steps
+                .as_ref()
+                .map(|steps| steps.iter().map(|s| s.resolve_paths()).collect()),
             name: self.name.clone(),
             description: self.description.clone(),
             root: self.root.clone(),
             fileset: self.fileset.clone(),
             plans: self.plans.clone(),
+            rtl_only: self.rtl_only.clone(),
+            env: self.env.clone(),
+            isolate_env: self.isolate_env.clone(),
+            keep_env: self.keep_env.clone(),
+            artifacts: self.artifacts.clone(),
+            timeout: self.timeout.clone(),
+            retries: self.retries.clone(),
+            pass_pattern: self.pass_pattern.clone(),
+            fail_pattern: self.fail_pattern.clone(),
+            languages: self.languages.clone(),
         };
 
         write!(f, "{}", toml::to_string_pretty(&refreshed_target).unwrap())
     }
 }
 
+/// Tracks the lines of a run's output against a target's pass and fail
+/// patterns as the lines are produced.
+#[derive(Debug, Clone)]
+pub struct OutputCheck {
+    pass: Option<Regex>,
+    fail: Option<Regex>,
+    passed: bool,
+    failed: Option<String>,
+}
+
+impl OutputCheck {
+    /// Checks the next `line` of output against the patterns.
+    pub fn inspect(&mut self, line: &str) {
+        if let Some(fail) = &self.fail {
+            if self.failed.is_none() && fail.is_match(line) == true {
+                self.failed = Some(line.trim().to_string());
+            }
+        }
+        if let Some(pass) = &self.pass {
+            if self.passed == false && pass.is_match(line) == true {
+                self.passed = true;
+            }
+        }
+    }
+
+    /// Determines if the output inspected so far passes.
+    ///
+    /// The output fails if any line matched the fail pattern, or if no line
+    /// matched the pass pattern.
+    pub fn verdict(&self) -> Result<(), Error> {
+        if let Some(line) = &self.failed {
+            return Err(Error::TargetOutputMatchedFail(line.clone()));
+        }
+        if let Some(pass) = &self.pass {
+            if self.passed == false {
+                return Err(Error::TargetOutputMissingPass(pass.as_str().to_string()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A hdl language supported by a target, optionally limited to a single
+/// language standard.
+///
+/// It is written as the language's name, followed by a `:` and the standard
+/// when limited, such as `vhdl:2008`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LangSupport {
+    lang: Lang,
+    std: Option<String>,
+}
+
+impl LangSupport {
+    /// Checks if a file written in the `lang` with the standard `std` is
+    /// supported.
+    ///
+    /// A file that does not declare its standard is supported by any standard
+    /// of its language.
+    pub fn accepts(&self, lang: &Lang, std: &Option<String>) -> bool {
+        if &self.lang != lang {
+            return false;
+        }
+        match (&self.std, std) {
+            (Some(supported), Some(s)) => strcmp::cmp_ascii_ignore_case(supported, s),
+            _ => true,
+        }
+    }
+}
+
+impl FromStr for LangSupport {
+    type Err = AnyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (lang, std) = match s.split_once(':') {
+            Some((l, std)) => match std.trim().is_empty() {
+                true => return Err(AnyError(format!("missing standard after ':' in {:?}", s))),
+                false => (l, Some(std.trim().to_string())),
+            },
+            None => (s, None),
+        };
+        Ok(Self {
+            lang: Lang::from_str(&lang.trim().to_ascii_lowercase())?,
+            std: std,
+        })
+    }
+}
+
+impl std::fmt::Display for LangSupport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.std {
+            Some(std) => write!(f, "{}:{}", self.lang, std),
+            None => write!(f, "{}", self.lang),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for LangSupport {
+    fn deserialize<D>(deserializer: D) -> Result<LangSupport, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct LayerVisitor;
+
+        impl<'de> de::Visitor<'de> for LayerVisitor {
+            type Value = LangSupport;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a hdl language with an optional standard")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                match LangSupport::from_str(v) {
+                    Ok(v) => Ok(v),
+                    Err(e) => Err(de::Error::custom(e)),
+                }
+            }
+        }
+
+        deserializer.deserialize_str(LayerVisitor)
+    }
+}
+
+impl serde::Serialize for LangSupport {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// A single stage in a target's pipeline of steps.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Step {
+    name: String,
+    command: String,
+    args: Option<Vec<String>>,
+    /// The working directory, relative to the target's output directory
+    dir: Option<String>,
+    timeout: Option<u64>,
+    retries: Option<u32>,
+    #[serde(skip_serializing, skip_deserializing)]
+    root: PathBuf,
+}
+
+impl Step {
+    /// Performs variable substitution on the arguments and working directory.
+    fn replace_vars(mut self, vtable: &StrSwapTable) -> Self {
+        self.args = self.args.map(|args| {
+            args.into_iter()
+                .map(|arg| swap::substitute(arg, vtable))
+                .collect()
+        });
+        self.dir = self.dir.map(|d| swap::substitute(d, vtable));
+        self
+    }
+
+    /// Uses the target's `timeout` and `retries` when the step does not set
+    /// its own.
+    fn inherit_limits(mut self, timeout: Option<u64>, retries: Option<u32>) -> Self {
+        self.timeout = self.timeout.or(timeout);
+        self.retries = self.retries.or(retries);
+        self
+    }
+
+    /// Writes out the full paths for the command and arguments that are
+    /// relative to the step's root.
+    fn resolve_paths(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            command: filesystem::resolve_rel_path(&self.root, &self.command),
+            args: self.args.as_ref().map(|args| {
+                args.iter()
+                    .map(|a| filesystem::resolve_rel_path(&self.root, a))
+                    .collect()
+            }),
+            dir: self.dir.clone(),
+            timeout: self.timeout,
+            retries: self.retries,
+            root: self.root.clone(),
+        }
+    }
+}
+
+impl Process for Step {
+    fn get_root(&self) -> &PathBuf {
+        &self.root
+    }
+
+    fn get_args(&self) -> Vec<&String> {
+        match &self.args {
+            Some(list) => list.iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn get_command(&self) -> Result<&String, Error> {
+        Ok(&self.command)
+    }
+
+    fn get_timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+
+    fn get_retries(&self) -> u32 {
+        self.retries.unwrap_or(0)
+    }
+}
+
+/// Quotes the `text` as a single word for a POSIX shell.
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
 impl FromStr for Target {
     type Err = toml::de::Error;
 
@@ -188,23 +939,34 @@ impl FromStr for Target {
 pub trait Process {
     fn get_root(&self) -> &PathBuf;
 
-    fn get_command(&self) -> &String;
+    /// References the command to run, which must be defined.
+    fn get_command(&self) -> Result<&String, Error>;
 
     fn get_args(&self) -> Vec<&String>;
 
-    /// Runs the given `command` with the set `args` for the plugin.
-    fn execute(
+    /// The number of seconds to allow the process to run before it is stopped.
+    fn get_timeout(&self) -> Option<u64> {
+        None
+    }
+
+    /// The number of times to run the process again after it fails.
+    fn get_retries(&self) -> u32 {
+        0
+    }
+
+    /// Determines the command and arguments to run, where the paths that are
+    /// relative to the process's root are written out in full.
+    ///
+    /// The `overloaded_command` replaces the defined command, and the
+    /// `extra_args` follow the defined arguments.
+    fn resolve_invocation(
         &self,
         overloaded_command: &Option<String>,
         extra_args: &[String],
-        verbose: bool,
-        cwd: &PathBuf,
-        envs: HashMap<&String, &String>,
-    ) -> Result<(), Fault> {
-        // resolve the relative paths in the command and arguments defined in original configuration
+    ) -> Result<(String, Vec<String>), Error> {
         let command = match overloaded_command {
             Some(c) => c,
-            None => self.get_command(),
+            None => self.get_command()?,
         };
 
         let root_path = self.get_root();
@@ -218,7 +980,31 @@ pub trait Process {
             .collect();
 
         // append args set on the command-line to the base-line of arguments
-        let args = [&arguments, extra_args].concat();
+        Ok((command, [&arguments, extra_args].concat()))
+    }
+
+    /// Runs the given `command` with the set `args` for the plugin.
+    ///
+    /// When `isolate` is `true`, the process only receives the variables in
+    /// `envs` instead of inheriting the current environment.
+    ///
+    /// A process that fails or exceeds its timeout is ran again for each of its
+    /// retries. A process is never retried once orbit is interrupted.
+    ///
+    /// When a `log` is given, each attempt is recorded in the log along with
+    /// the process's output.
+    fn execute(
+        &self,
+        overloaded_command: &Option<String>,
+        extra_args: &[String],
+        verbose: bool,
+        cwd: &PathBuf,
+        envs: HashMap<&String, &String>,
+        isolate: bool,
+        log: Option<&LogFile>,
+    ) -> Result<(), Fault> {
+        // resolve the relative paths in the command and arguments defined in original configuration
+        let (command, args) = self.resolve_invocation(overloaded_command, extra_args)?;
         // display the literal command being ran
         if verbose == true {
             let s = args
@@ -226,23 +1012,65 @@ pub trait Process {
                 .fold(String::new(), |x, y| x + "\"" + &y + "\" ");
             crate::info!("running: {} {}", command, s);
         }
-        let mut proc = filesystem::invoke(
-            cwd,
-            &command,
-            &args,
-            Context::enable_windows_bat_file_match(),
-            envs,
-        )?;
-        let exit_code = proc.wait()?;
-        match exit_code.code() {
-            Some(num) => {
-                if num != 0 {
-                    Err(Error::ChildProcErrorCode(num))?
-                } else {
-                    Ok(())
+        let timeout = self.get_timeout();
+        let attempts = self.get_retries() + 1;
+        let mut attempt = 1;
+        loop {
+            if let Some(log) = log {
+                log.begin_attempt(attempt);
+                if attempt > 1 {
+                    log.write_note(&format!("# attempt {} of {}", attempt, attempts))?;
                 }
+                log.write_header(&command, &args, cwd, &envs)?;
+            }
+            let mut proc = filesystem::invoke(
+                cwd,
+                &command,
+                &args,
+                Context::enable_windows_bat_file_match(),
+                envs.clone(),
+                isolate,
+                timeout.is_some(),
+                log.is_some(),
+            )?;
+            let readers = match log {
+                Some(log) => log.tee(&mut proc),
+                None => Vec::new(),
+            };
+            let outcome = subprocess::wait(&mut proc, timeout.map(Duration::from_secs))?;
+            // wait for the remaining output to be recorded
+            for r in readers {
+                let _ = r.join();
+            }
+            let result = match outcome {
+                Outcome::Exited(status) => match status.code() {
+                    Some(0) => Ok(()),
+                    Some(num) => Err(Error::ChildProcErrorCode(num)),
+                    None => Err(Error::ChildProcTerminated),
+                },
+                Outcome::TimedOut => Err(Error::ChildProcTimedOut(timeout.unwrap())),
+                Outcome::Interrupted => Err(Error::ChildProcInterrupted),
+            };
+            if let Some(log) = log {
+                log.write_note(&format!(
+                    "# result: {}",
+                    match &result {
+                        Ok(()) => String::from("success"),
+                        Err(e) => e.to_string(),
+                    }
+                ))?;
+            }
+            match result {
+                Ok(()) => return Ok(()),
+                Err(Error::ChildProcInterrupted) => return Err(Error::ChildProcInterrupted)?,
+                Err(e) => match attempt < attempts {
+                    true => {
+                        crate::warn!("attempt {} of {} {}; retrying", attempt, attempts, e);
+                        attempt += 1;
+                    }
+                    false => return Err(e)?,
+                },
             }
-            None => Err(Error::ChildProcTerminated)?,
         }
     }
 }
@@ -259,8 +1087,18 @@ impl Process for Target {
         }
     }
 
-    fn get_command(&self) -> &String {
-        &self.command
+    fn get_command(&self) -> Result<&String, Error> {
+        self.command
+            .as_ref()
+            .ok_or_else(|| Error::TargetMissingCommand(self.name.clone()))
+    }
+
+    fn get_timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+
+    fn get_retries(&self) -> u32 {
+        self.retries.unwrap_or(0)
     }
 }
 
@@ -300,6 +1138,7 @@ fileset.text = "*.txt"
 name = "ffi"
 command = "bash"
 args = ["~/scripts/download.bash"]    
+rtl-only = true
 "#;
 
     #[test]
@@ -309,19 +1148,31 @@ args = ["~/scripts/download.bash"]
             plug,
             Target {
                 name: String::from("ghdl"),
-                command: String::from("python"),
+                extends: None,
+                steps: None,
+                command: Some(String::from("python")),
                 plans: None,
                 args: Some(vec![String::from("./scripts/ghdl.py")]),
                 description: Some(String::from(
                     "Backend script for simulating VHDL with GHDL."
                 )),
-                fileset: Some(HashMap::from([
+                fileset: Some(BTreeMap::from([
                     (
                         String::from("py-model"),
                         Style::from_str("{{orbit.bench}}.py").unwrap()
                     ),
                     (String::from("text"), Style::from_str("*.txt").unwrap()),
                 ])),
+                rtl_only: None,
+                env: None,
+                isolate_env: None,
+                keep_env: None,
+                artifacts: None,
+                timeout: None,
+                retries: None,
+                pass_pattern: None,
+                fail_pattern: None,
+                languages: None,
                 root: None,
             }
         );
@@ -331,16 +1182,374 @@ args = ["~/scripts/download.bash"]
             plug,
             Target {
                 name: String::from("ffi"),
-                command: String::from("bash"),
+                extends: None,
+                steps: None,
+                command: Some(String::from("bash")),
                 args: Some(vec![String::from("~/scripts/download.bash")]),
                 description: None,
                 plans: None,
                 fileset: None,
+                rtl_only: Some(true),
+                env: None,
+                isolate_env: None,
+                keep_env: None,
+                artifacts: None,
+                timeout: None,
+                retries: None,
+                pass_pattern: None,
+                fail_pattern: None,
+                languages: None,
                 root: None,
             }
         );
     }
 
+    const P_3: &str = r#"
+name = "ghdl"
+plans = ["json"]
+
+[[steps]]
+name = "analyze"
+command = "ghdl"
+args = ["-a", "--std=08"]
+
+[[steps]]
+name = "simulate"
+command = "ghdl"
+args = ["-r", "{{ orbit.tb.name }}"]
+dir = "sim"
+"#;
+
+    #[test]
+    fn steps_pipeline() {
+        let target = Target::from_str(P_3).unwrap();
+        assert_eq!(target.validate(), Ok(()));
+
+        let steps = target.steps.as_ref().unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[1].get_command().unwrap(), "ghdl");
+        assert_eq!(steps[1].dir, Some(String::from("sim")));
+
+        // the range of steps is checked before running any step
+        let run = |from: Option<&str>, to: Option<&str>| {
+            target
+                .clone()
+                .root(PathBuf::new())
+                .run(
+                    &None,
+                    &[],
+                    false,
+                    &PathBuf::new(),
+                    HashMap::new(),
+                    &from.map(String::from),
+                    &to.map(String::from),
+                    None,
+                )
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            run(Some("compile"), None),
+            Error::TargetStepNotFound(
+                String::from("ghdl"),
+                String::from("compile"),
+                String::from("analyze, simulate")
+            )
+            .to_string()
+        );
+        assert_eq!(
+            run(Some("simulate"), Some("analyze")),
+            Error::TargetStepRangeInvalid(String::from("simulate"), String::from("analyze"))
+                .to_string()
+        );
+
+        // a target cannot run both a command and steps
+        let target = Target::from_str(&format!("command = \"make\"\n{}", P_3)).unwrap();
+        assert_eq!(
+            target.validate(),
+            Err(Error::TargetCommandAndSteps(String::from("ghdl")))
+        );
+
+        // the step names must be unique
+        let target = Target::from_str(&P_3.replace("simulate", "analyze")).unwrap();
+        assert_eq!(
+            target.validate(),
+            Err(Error::TargetStepDuplicate(
+                String::from("ghdl"),
+                String::from("analyze")
+            ))
+        );
+    }
+
+    #[test]
+    fn extend_environment() {
+        let base = Target::from_str(
+            r#"
+name = "sim"
+command = "vsim"
+isolate-env = true
+keep-env = ["LM_LICENSE_FILE"]
+env.MODELSIM = "/opt/modelsim.ini"
+env.WORK = "work"
+"#,
+        )
+        .unwrap();
+        let target = Target::from_str(
+            r#"
+name = "sim-gui"
+extends = "sim"
+keep-env = ["DISPLAY"]
+env.WORK = "{{ orbit.tb.name }}"
+"#,
+        )
+        .unwrap()
+        .extend(&base);
+
+        assert_eq!(target.isolate_env, Some(true));
+        assert_eq!(
+            target.keep_env,
+            Some(vec![
+                String::from("LM_LICENSE_FILE"),
+                String::from("DISPLAY")
+            ])
+        );
+
+        let mut vtable = StrSwapTable::new();
+        vtable.add("orbit.tb.name", "alu_tb");
+        let target = target.replace_vars_in_args(&vtable);
+        assert_eq!(
+            target.env,
+            Some(BTreeMap::from([
+                (String::from("MODELSIM"), String::from("/opt/modelsim.ini")),
+                (String::from("WORK"), String::from("alu_tb")),
+            ]))
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn timeout_and_retries() {
+        let dir = tempfile::tempdir().unwrap();
+        let cwd = dir.path().to_path_buf();
+        let run = |target: &Target| {
+            target.run(&None, &[], false, &cwd, HashMap::new(), &None, &None, None)
+        };
+
+        // a failing process is ran again for each retry
+        let target = Target::from_str(
+            r#"
+name = "flaky"
+command = "sh"
+args = ["-c", "echo run >> runs.txt; exit 1"]
+retries = 2
+"#,
+        )
+        .unwrap()
+        .root(cwd.clone());
+        assert_eq!(
+            run(&target).unwrap_err().to_string(),
+            Error::ChildProcErrorCode(1).to_string()
+        );
+        assert_eq!(
+            std::fs::read_to_string(cwd.join("runs.txt")).unwrap(),
+            "run\nrun\nrun\n"
+        );
+
+        // a retry is checked without the output of the failed attempts
+        let target = Target::from_str(
+            r#"
+name = "flaky-sim"
+command = "sh"
+args = ["-c", "if [ -e tried ]; then echo 'All tests passed'; else touch tried; echo 'Error: x'; exit 1; fi"]
+retries = 1
+pass-pattern = "^All tests passed"
+fail-pattern = "Error:"
+"#,
+        )
+        .unwrap()
+        .root(cwd.clone());
+        let log = LogFile::memory().checking(target.output_check().unwrap());
+        target
+            .run(
+                &None,
+                &[],
+                false,
+                &cwd,
+                HashMap::new(),
+                &None,
+                &None,
+                Some(&log),
+            )
+            .unwrap();
+        assert_eq!(log.verdict().is_ok(), true);
+
+        // the steps use the target's timeout
+        let target = Target::from_str(
+            r#"
+name = "hang"
+timeout = 1
+
+[[steps]]
+name = "wait"
+command = "sleep"
+args = ["10"]
+"#,
+        )
+        .unwrap()
+        .root(cwd.clone());
+        assert_eq!(
+            run(&target).unwrap_err().to_string(),
+            Error::TargetStepFailed(
+                String::from("wait"),
+                1,
+                1,
+                LastError(Error::ChildProcTimedOut(1).to_string())
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn invoke_without_command() {
+        let mut target = Target::from_str("name = \"empty\"\n").unwrap();
+        target.set_root(PathBuf::from("/tmp"));
+        assert_eq!(
+            target
+                .resolve_invocation(&None, &[])
+                .unwrap_err()
+                .to_string(),
+            Error::TargetMissingCommand(String::from("empty")).to_string()
+        );
+        // the command can still be given from the command-line
+        assert_eq!(
+            target
+                .resolve_invocation(&Some(String::from("echo")), &[String::from("hi")])
+                .unwrap(),
+            (String::from("echo"), vec![String::from("hi")])
+        );
+    }
+
+    #[test]
+    fn output_patterns() {
+        let target = Target::from_str(
+            r#"
+name = "sim"
+command = "vsim"
+pass-pattern = "^All tests passed"
+fail-pattern = "(Error|Failure):"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            target.check_output("running\nAll tests passed\n").is_ok(),
+            true
+        );
+        assert_eq!(
+            target
+                .check_output("# ** Error: x != 1\nAll tests passed\n")
+                .unwrap_err()
+                .to_string(),
+            Error::TargetOutputMatchedFail(String::from("# ** Error: x != 1")).to_string()
+        );
+        assert_eq!(
+            target.check_output("done\n").unwrap_err().to_string(),
+            Error::TargetOutputMissingPass(String::from("^All tests passed")).to_string()
+        );
+
+        // the patterns are inherited and must be valid
+        let child = Target::from_str(
+            r#"
+name = "sim-fast"
+extends = "sim"
+fail-pattern = "(unclosed"
+"#,
+        )
+        .unwrap()
+        .extend(&target);
+        assert_eq!(child.pass_pattern, Some(String::from("^All tests passed")));
+        assert_eq!(child.verify_patterns().is_err(), true);
+    }
+
+    #[test]
+    fn reproduction_script() {
+        let target = Target::from_str(P_3).unwrap().root(PathBuf::from("/cfg"));
+        let cwd = PathBuf::from("/out");
+        let (key, value) = (String::from("ORBIT_TB_NAME"), String::from("it's_tb"));
+        let envs = HashMap::from([(&key, &value)]);
+        let script = target
+            .to_script(&None, &[], &cwd, envs.clone(), &None, &None)
+            .unwrap();
+        assert_eq!(script.starts_with("#!/bin/sh\n"), true);
+        assert_eq!(
+            script.contains("export ORBIT_TB_NAME='it'\\''s_tb'\n"),
+            true
+        );
+        assert_eq!(
+            script.contains("# step: analyze (1 of 2)\ncd '/out'\n'ghdl' '-a' '--std=08'\n"),
+            true
+        );
+        assert_eq!(
+            script.contains("mkdir -p '/out/sim'\ncd '/out/sim'\n'ghdl' '-r'"),
+            true
+        );
+
+        // only the selected range of steps is reproduced
+        let script = target
+            .to_script(
+                &None,
+                &[],
+                &cwd,
+                envs,
+                &Some(String::from("simulate")),
+                &None,
+            )
+            .unwrap();
+        assert_eq!(script.contains("analyze"), false);
+        assert_eq!(
+            target
+                .to_script(
+                    &Some(String::from("nvc")),
+                    &[],
+                    &cwd,
+                    HashMap::new(),
+                    &None,
+                    &None
+                )
+                .unwrap_err(),
+            Error::TargetStepsNoOverride(String::from("ghdl"))
+        );
+    }
+
+    #[test]
+    fn language_support() {
+        let target = Target::from_str(
+            r#"
+name = "synth"
+command = "yosys"
+languages = ["verilog", "vhdl:2008"]
+"#,
+        )
+        .unwrap();
+        let std = |s: &str| Some(String::from(s));
+        assert_eq!(target.supports_language(&Lang::Verilog, &std("2005")), true);
+        assert_eq!(target.supports_language(&Lang::Vhdl, &std("2008")), true);
+        assert_eq!(target.supports_language(&Lang::Vhdl, &None), true);
+        assert_eq!(target.supports_language(&Lang::Vhdl, &std("93")), false);
+        assert_eq!(target.supports_language(&Lang::SystemVerilog, &None), false);
+        assert_eq!(
+            target.get_languages().unwrap()[1].to_string(),
+            String::from("vhdl:2008")
+        );
+
+        // every language is supported when none are listed
+        let target = Target::from_str("name = \"sim\"\ncommand = \"ghdl\"").unwrap();
+        assert_eq!(target.supports_language(&Lang::SystemVerilog, &None), true);
+
+        assert_eq!(LangSupport::from_str("VHDL").unwrap().to_string(), "vhdl");
+        assert_eq!(LangSupport::from_str("vhdl:").is_err(), true);
+        assert_eq!(LangSupport::from_str("verilog-ams").is_err(), true);
+    }
+
     #[test]
     fn series_of_plugins() {
         let contents = format!("{0}{1}\n{0}{2}", "[[plugin]]", P_1, P_2);
@@ -100,10 +100,7 @@ pub fn get_target_version<'a>(
     let mut latest_version: Option<&Version> = None;
     space
         .into_iter()
-        .filter(|f| match &ver {
-            AnyVersion::Specific(v) => crate::core::version::is_compatible(v, f),
-            AnyVersion::Latest => true,
-        })
+        .filter(|f| ver.matches(f))
         .for_each(|tag| {
             if latest_version.is_none() || *tag > latest_version.as_ref().unwrap() {
                 latest_version = Some(tag);
@@ -121,6 +118,7 @@ pub fn get_target_version<'a>(
 #[derive(Debug, Eq, Hash, Clone, PartialEq, Ord, PartialOrd)]
 pub enum AnyVersion {
     Specific(PartialVersion),
+    Req(VersionReq),
     Latest,
 }
 
@@ -129,6 +127,7 @@ impl std::fmt::Display for AnyVersion {
         match self {
             Self::Latest => write!(f, "latest"),
             Self::Specific(v) => write!(f, "{}", v),
+            Self::Req(r) => write!(f, "{}", r),
         }
     }
 }
@@ -139,7 +138,7 @@ impl std::str::FromStr for AnyVersion {
         if crate::util::strcmp::cmp_ascii_ignore_case(s, "latest") {
             Ok(Self::Latest)
         } else {
-            Ok(Self::Specific(PartialVersion::from_str(s)?))
+            Ok(Self::from(&VersionReq::from_str(s)?))
         }
     }
 }
@@ -155,6 +154,24 @@ impl AnyVersion {
     pub fn is_latest(&self) -> bool {
         self == &Self::Latest
     }
+
+    /// Checks if the version `ver` satisfies `self`.
+    pub fn matches(&self, ver: &Version) -> bool {
+        match self {
+            Self::Specific(v) => is_compatible(v, ver),
+            Self::Req(r) => r.matches(ver),
+            Self::Latest => true,
+        }
+    }
+}
+
+impl From<&VersionReq> for AnyVersion {
+    fn from(value: &VersionReq) -> Self {
+        match value.as_partial() {
+            Some(v) => Self::Specific(v.clone()),
+            None => Self::Req(value.clone()),
+        }
+    }
 }
 
 impl From<&Version> for AnyVersion {
@@ -381,6 +398,257 @@ impl Serialize for PartialVersion {
     }
 }
 
+/// A requirement that versions must satisfy, written as one or more
+/// comparators joined by commas, such as `>=1.4.2, <2, !=1.5.0`.
+///
+/// A comparator without an operator keeps the meaning of a partial version,
+/// where the version must be compatible with it (see [is_compatible]).
+#[derive(Debug, PartialEq, PartialOrd, Clone, Eq, Ord, Hash)]
+pub struct VersionReq(Vec<Comparator>);
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Eq, Ord, Hash)]
+struct Comparator {
+    op: Op,
+    version: PartialVersion,
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Eq, Ord, Hash)]
+enum Op {
+    Compatible,
+    Exact,
+    NotEqual,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+}
+
+/// The operators of a comparator, where longer operators are listed before the
+/// shorter operators they begin with.
+const OPERATORS: [(&str, Op); 8] = [
+    (">=", Op::GreaterEq),
+    ("<=", Op::LessEq),
+    ("!=", Op::NotEqual),
+    (">", Op::Greater),
+    ("<", Op::Less),
+    ("=", Op::Exact),
+    ("~", Op::Tilde),
+    ("^", Op::Caret),
+];
+
+impl Op {
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Compatible => "",
+            _ => OPERATORS.iter().find(|(_, op)| op == self).unwrap().0,
+        }
+    }
+
+    /// Checks if the operator accepts versions across a range of numbers,
+    /// instead of matching a version's numbers.
+    fn is_range(&self) -> bool {
+        match self {
+            Self::Compatible | Self::Exact | Self::NotEqual => false,
+            _ => true,
+        }
+    }
+}
+
+impl Comparator {
+    fn matches(&self, ver: &Version) -> bool {
+        let pv = &self.version;
+        // the lowest version that is compatible with the partial version
+        let lower = Version::from(pv.clone());
+        // the lowest version above every version compatible with the partial version
+        let upper = match (pv.minor, pv.micro) {
+            (None, _) => Some(Self::bound(pv.major.checked_add(1), 0)),
+            (Some(m), None) => Some(Self::bound(
+                Some(pv.major),
+                m.checked_add(1).unwrap_or(VerNum::MAX),
+            )),
+            _ => None,
+        };
+        match self.op {
+            Op::Compatible | Op::Exact => is_compatible(pv, ver),
+            Op::NotEqual => is_compatible(pv, ver) == false,
+            Op::Greater => match upper {
+                Some(u) => ver >= &u,
+                None => ver > &lower,
+            },
+            Op::GreaterEq => ver >= &lower,
+            Op::Less => ver < &lower,
+            Op::LessEq => match upper {
+                Some(u) => ver < &u,
+                None => ver <= &lower,
+            },
+            Op::Tilde => {
+                let limit = match pv.minor {
+                    Some(m) => Self::bound(Some(pv.major), m.checked_add(1).unwrap_or(VerNum::MAX)),
+                    None => Self::bound(pv.major.checked_add(1), 0),
+                };
+                ver >= &lower && ver < &limit
+            }
+            Op::Caret => {
+                // the left-most non-zero number cannot change
+                let limit = match (pv.major, pv.minor, pv.micro) {
+                    (0, Some(0), Some(p)) => {
+                        Version::new().micro(p.checked_add(1).unwrap_or(VerNum::MAX))
+                    }
+                    (0, Some(m), _) => {
+                        Self::bound(Some(0), m.checked_add(1).unwrap_or(VerNum::MAX))
+                    }
+                    (maj, _, _) => Self::bound(maj.checked_add(1), 0),
+                };
+                ver >= &lower && ver < &limit
+            }
+        }
+    }
+
+    /// Creates the version `major.minor.0`, where a `major` that does not fit
+    /// is the highest possible version.
+    fn bound(major: Option<VerNum>, minor: VerNum) -> Version {
+        match major {
+            Some(m) => Version::new().major(m).minor(minor),
+            None => Version::new()
+                .major(VerNum::MAX)
+                .minor(VerNum::MAX)
+                .micro(VerNum::MAX),
+        }
+    }
+}
+
+impl FromStr for Comparator {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(VersionError::EmptyComparator);
+        }
+        let (op, rest) = match OPERATORS.iter().find(|(sym, _)| s.starts_with(sym)) {
+            Some((sym, op)) => (*op, &s[sym.len()..]),
+            None => (Op::Compatible, s),
+        };
+        Ok(Self {
+            op: op,
+            version: PartialVersion::from_str(rest)?,
+        })
+    }
+}
+
+impl Display for Comparator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.op.as_str(), self.version)
+    }
+}
+
+impl VersionReq {
+    /// Checks if the version `ver` satisfies every comparator.
+    ///
+    /// A version with a label only satisfies a range of versions when a
+    /// comparator names a label for the same version numbers, so pre-releases
+    /// are only chosen when asked for.
+    pub fn matches(&self, ver: &Version) -> bool {
+        if ver.has_label() == true && self.0.iter().any(|c| c.op.is_range()) == true {
+            let allowed = self.0.iter().any(|c| {
+                c.version.has_label() == true
+                    && Version::from(c.version.clone()).label(None) == ver.clone().label(None)
+            });
+            if allowed == false {
+                return false;
+            }
+        }
+        self.0.iter().all(|c| c.matches(ver))
+    }
+
+    /// Returns the partial version when the requirement is only a single
+    /// partial version without an operator.
+    pub fn as_partial(&self) -> Option<&PartialVersion> {
+        match self.0.as_slice() {
+            [c] if c.op == Op::Compatible => Some(&c.version),
+            _ => None,
+        }
+    }
+}
+
+impl From<PartialVersion> for VersionReq {
+    fn from(value: PartialVersion) -> Self {
+        Self(vec![Comparator {
+            op: Op::Compatible,
+            version: value,
+        }])
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Err(VersionError::EmptyVersion);
+        }
+        Ok(Self(
+            s.split(',')
+                .map(|c| Comparator::from_str(c))
+                .collect::<Result<Vec<Comparator>, VersionError>>()?,
+        ))
+    }
+}
+
+impl Display for VersionReq {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.0
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionReq {
+    fn deserialize<D>(deserializer: D) -> Result<VersionReq, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct LayerVisitor;
+
+        impl<'de> de::Visitor<'de> for LayerVisitor {
+            type Value = VersionReq;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a semantic version requirement")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                match VersionReq::from_str(v) {
+                    Ok(v) => Ok(v),
+                    Err(e) => Err(de::Error::custom(e)),
+                }
+            }
+        }
+
+        deserializer.deserialize_map(LayerVisitor)
+    }
+}
+
+impl Serialize for VersionReq {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Ord, Eq, Hash)]
 pub struct Version {
     major: VerNum,
@@ -612,6 +880,7 @@ pub enum VersionError {
     InvalidDigit(ParseIntError),
     EmptyLabel,
     InvalidChar(char),
+    EmptyComparator,
 }
 
 impl std::error::Error for VersionError {}
@@ -632,6 +901,7 @@ impl Display for VersionError {
             InvalidChar(c) => write!(f, "invalid character '{}' in version label", c),
             ExtraLevels(l) => write!(f, "too many version positions; found {} expected 3", l),
             InvalidDigit(_) => write!(f, "invalid digit in version"),
+            EmptyComparator => write!(f, "empty comparator in version requirement"),
         }
     }
 }
@@ -996,6 +1266,117 @@ mod test {
         assert_eq!(v.to_string(), "20.4.7");
     }
 
+    mod version_req {
+        use super::*;
+
+        fn req(s: &str) -> VersionReq {
+            VersionReq::from_str(s).unwrap()
+        }
+
+        fn ver(s: &str) -> Version {
+            Version::from_str(s).unwrap()
+        }
+
+        #[test]
+        fn from_str() {
+            assert_eq!(req(">=1.4.2,<2").to_string(), ">=1.4.2, <2");
+            assert_eq!(req(" ^ 0.3 ").to_string(), "^0.3");
+            assert_eq!(
+                req("1.2").as_partial(),
+                Some(&PartialVersion::new().major(1).minor(2))
+            );
+            assert_eq!(req("=1.2").as_partial(), None);
+            assert_eq!(VersionReq::from_str(""), Err(VersionError::EmptyVersion));
+            assert_eq!(
+                VersionReq::from_str(">=1.0,"),
+                Err(VersionError::EmptyComparator)
+            );
+            assert_eq!(VersionReq::from_str(">=a").is_err(), true);
+            assert_eq!(VersionReq::from_str("<=1.0.0.0").is_err(), true);
+        }
+
+        #[test]
+        fn partial_keeps_meaning() {
+            for (r, v) in [("1", "1.9.3"), ("1.2", "1.2.0"), ("1.2.3", "1.2.3")] {
+                assert_eq!(req(r).matches(&ver(v)), true);
+                assert_eq!(
+                    req(r).matches(&ver(v)),
+                    is_compatible(&PartialVersion::from_str(r).unwrap(), &ver(v))
+                );
+            }
+            assert_eq!(req("1.2").matches(&ver("1.3.0")), false);
+            assert_eq!(req("=1.2").matches(&ver("1.2.9")), true);
+        }
+
+        #[test]
+        fn comparisons() {
+            let r = req(">=1.4.2, <2");
+            assert_eq!(r.matches(&ver("1.4.1")), false);
+            assert_eq!(r.matches(&ver("1.4.2")), true);
+            assert_eq!(r.matches(&ver("1.99.0")), true);
+            assert_eq!(r.matches(&ver("2.0.0")), false);
+
+            // partial versions cover every version they are compatible with
+            assert_eq!(req(">1.2").matches(&ver("1.2.9")), false);
+            assert_eq!(req(">1.2").matches(&ver("1.3.0")), true);
+            assert_eq!(req(">1.2.3").matches(&ver("1.2.4")), true);
+            assert_eq!(req("<=1.2").matches(&ver("1.2.9")), true);
+            assert_eq!(req("<=1.2").matches(&ver("1.3.0")), false);
+            assert_eq!(req("<1.2").matches(&ver("1.1.9")), true);
+            assert_eq!(req("<1.2").matches(&ver("1.2.0")), false);
+        }
+
+        #[test]
+        fn tilde_and_caret() {
+            assert_eq!(req("~1.2.3").matches(&ver("1.2.9")), true);
+            assert_eq!(req("~1.2.3").matches(&ver("1.3.0")), false);
+            assert_eq!(req("~1").matches(&ver("1.9.0")), true);
+            assert_eq!(req("~1").matches(&ver("2.0.0")), false);
+
+            assert_eq!(req("^1.2.3").matches(&ver("1.9.0")), true);
+            assert_eq!(req("^1.2.3").matches(&ver("1.2.2")), false);
+            assert_eq!(req("^1.2.3").matches(&ver("2.0.0")), false);
+            assert_eq!(req("^0.2.3").matches(&ver("0.2.9")), true);
+            assert_eq!(req("^0.2.3").matches(&ver("0.3.0")), false);
+            assert_eq!(req("^0.0.3").matches(&ver("0.0.3")), true);
+            assert_eq!(req("^0.0.3").matches(&ver("0.0.4")), false);
+            assert_eq!(req("^0").matches(&ver("0.9.9")), true);
+        }
+
+        #[test]
+        fn exclusions_and_labels() {
+            let r = req("^1.4, !=1.5.0, !=1.6");
+            assert_eq!(r.matches(&ver("1.5.0")), false);
+            assert_eq!(r.matches(&ver("1.5.1")), true);
+            assert_eq!(r.matches(&ver("1.6.2")), false);
+            assert_eq!(r.matches(&ver("1.7.0")), true);
+
+            // labeled versions are only in a range when asked for
+            assert_eq!(req(">=1.0.0, <2").matches(&ver("1.5.0-beta")), false);
+            assert_eq!(req("<2").matches(&ver("2.0.0-rc")), false);
+            assert_eq!(req(">=2.0.0-beta").matches(&ver("2.0.0-rc")), true);
+            assert_eq!(req(">=2.0.0-beta").matches(&ver("2.0.0")), true);
+            assert_eq!(req("2").matches(&ver("2.0.0-rc")), true);
+        }
+
+        #[test]
+        fn any_version() {
+            assert_eq!(
+                AnyVersion::from_str("1.2").unwrap(),
+                AnyVersion::Specific(PartialVersion::new().major(1).minor(2))
+            );
+            assert_eq!(
+                AnyVersion::from_str(">=1.2, <2").unwrap(),
+                AnyVersion::Req(req(">=1.2, <2"))
+            );
+            assert_eq!(AnyVersion::from_str("LATEST").unwrap(), AnyVersion::Latest);
+            assert_eq!(
+                AnyVersion::from_str("~1.2").unwrap().matches(&ver("1.2.5")),
+                true
+            );
+        }
+    }
+
     #[test]
     fn partial_ver_cmp() {
         let v0 = PartialVersion::new().major(1);
@@ -0,0 +1,276 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Runs a command again every time the files of the working ip change.
+//!
+//! The files are polled for changes to their modification times and sizes, so
+//! no native file notifications are required.
+
+use crate::core::ip::Ip;
+use crate::util::anyerror::Fault;
+use crate::util::filesystem;
+use crate::util::subprocess;
+use colored::Colorize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+/// The time to wait between checks for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The time the files must remain unchanged before running again, so a burst
+/// of changes (such as saving many files at once) only causes a single run.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// The maximum number of changed files to display before a run.
+const MAX_LISTED: usize = 3;
+
+/// The state of every watched file.
+#[derive(Debug, PartialEq, Default)]
+pub struct Snapshot(BTreeMap<String, (Option<SystemTime>, u64)>);
+
+impl Snapshot {
+    /// Records the modification time and size of every file of the ip at
+    /// `root` and of its relative dependencies.
+    ///
+    /// Only the files at `root` are recorded while its manifest cannot be
+    /// loaded, such as during an edit. Files within the `target_dir` are never
+    /// recorded because every run writes its outputs there.
+    pub fn capture(root: &PathBuf, target_dir: &PathBuf) -> Self {
+        let files = match Ip::load(root.clone(), true, false) {
+            Ok(ip) => {
+                let mut files = Vec::new();
+                let mut visited = HashSet::new();
+                Self::collect(&ip, true, &mut visited, &mut files);
+                files
+            }
+            Err(_) => filesystem::gather_current_files(root, false),
+        };
+        Self(
+            files
+                .into_iter()
+                .filter(|f| PathBuf::from(f).starts_with(target_dir) == false)
+                .map(|f| {
+                    let state = match std::fs::metadata(&f) {
+                        Ok(m) => (m.modified().ok(), m.len()),
+                        Err(_) => (None, 0),
+                    };
+                    (f, state)
+                })
+                .collect(),
+        )
+    }
+
+    /// Gathers the files of the `ip` and then of each of its relative
+    /// dependencies that have not been `visited`.
+    fn collect(ip: &Ip, is_working: bool, visited: &mut HashSet<PathBuf>, files: &mut Vec<String>) {
+        if visited.insert(ip.get_root().clone()) == false {
+            return;
+        }
+        files.extend(ip.gather_current_files());
+        for (_, dep) in ip.get_man().get_deps_list(is_working, false) {
+            if let Some(relative_ip) = dep.as_ip() {
+                Self::collect(relative_ip, false, visited, files);
+            }
+        }
+    }
+
+    /// Returns the number of watched files.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Lists the files that were added, removed, or modified in the `newer`
+    /// snapshot.
+    pub fn diff(&self, newer: &Self) -> Vec<String> {
+        let mut changed: Vec<String> = newer
+            .0
+            .iter()
+            .filter(|(f, state)| self.0.get(*f) != Some(state))
+            .map(|(f, _)| f.clone())
+            .collect();
+        changed.extend(
+            self.0
+                .keys()
+                .filter(|f| newer.0.contains_key(*f) == false)
+                .cloned(),
+        );
+        changed.sort();
+        changed
+    }
+}
+
+/// Runs `run` and then runs it again after every change to the files of the
+/// ip at `root` or of its relative dependencies, ignoring the files within the
+/// `target_dir`.
+///
+/// A failed run is reported without stopping, as the next change may fix it.
+/// This function never returns; orbit exits once it is interrupted.
+pub fn watch<F>(root: &PathBuf, target_dir: &PathBuf, mut run: F) -> !
+where
+    F: FnMut() -> Result<(), Fault>,
+{
+    let mut iteration: usize = 1;
+    loop {
+        // record the files before running so changes made during the run are noticed
+        let snapshot = Snapshot::capture(root, target_dir);
+        println!("{}", format!("=== run {} ===", iteration).bold());
+        let start = Instant::now();
+        match run() {
+            Ok(()) => crate::info!(
+                "run {} {} in {:.2}s",
+                iteration,
+                "succeeded".green(),
+                start.elapsed().as_secs_f64()
+            ),
+            Err(e) => {
+                eprintln!("{} {}", "error:".red().bold(), e);
+                crate::info!(
+                    "run {} {} in {:.2}s",
+                    iteration,
+                    "failed".red(),
+                    start.elapsed().as_secs_f64()
+                );
+            }
+        }
+        // an interrupt stops the watching along with the run
+        if subprocess::is_interrupted() == true {
+            std::process::exit(130);
+        }
+        crate::info!(
+            "watching {} files for changes; press ctrl+c to stop",
+            snapshot.len()
+        );
+        let changed = wait_for_changes(root, target_dir, snapshot);
+        crate::info!("detected changes to {}", summarize(root, &changed));
+        iteration += 1;
+    }
+}
+
+/// Blocks until the watched files differ from the `snapshot` and then remain
+/// unchanged for the debounce period, returning the changed files.
+fn wait_for_changes(root: &PathBuf, target_dir: &PathBuf, snapshot: Snapshot) -> Vec<String> {
+    let mut latest = loop {
+        std::thread::sleep(POLL_INTERVAL);
+        let current = Snapshot::capture(root, target_dir);
+        if snapshot.diff(&current).is_empty() == false {
+            break current;
+        }
+    };
+    loop {
+        std::thread::sleep(DEBOUNCE);
+        let current = Snapshot::capture(root, target_dir);
+        if latest.diff(&current).is_empty() == true {
+            break;
+        }
+        latest = current;
+    }
+    snapshot.diff(&latest)
+}
+
+/// Formats the `changed` files for display, relative to the `root` when
+/// possible.
+fn summarize(root: &PathBuf, changed: &[String]) -> String {
+    let names: Vec<String> = changed
+        .iter()
+        .take(MAX_LISTED)
+        .map(|f| filesystem::into_std_str(filesystem::remove_base(root, &PathBuf::from(f))))
+        .collect();
+    match changed.len() > MAX_LISTED {
+        true => format!(
+            "{} and {} other files",
+            names.join(", "),
+            changed.len() - MAX_LISTED
+        ),
+        false => names.join(", "),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn snapshot_diff() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let target_dir = root.join("target");
+        let (alu, mul) = (root.join("alu.vhd"), root.join("mul.vhd"));
+        std::fs::write(&alu, "entity alu is end;").unwrap();
+        let before = Snapshot::capture(&root, &target_dir);
+        assert_eq!(before.len(), 1);
+        assert_eq!(
+            before
+                .diff(&Snapshot::capture(&root, &target_dir))
+                .is_empty(),
+            true
+        );
+
+        std::fs::write(&alu, "entity alu is end entity;").unwrap();
+        std::fs::write(&mul, "entity mul is end;").unwrap();
+        let after = Snapshot::capture(&root, &target_dir);
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                filesystem::into_std_str(alu.clone()),
+                filesystem::into_std_str(mul.clone())
+            ]
+        );
+
+        std::fs::remove_file(&mul).unwrap();
+        assert_eq!(
+            after.diff(&Snapshot::capture(&root, &target_dir)),
+            vec![filesystem::into_std_str(mul)]
+        );
+        assert_eq!(
+            summarize(&root, &before.diff(&after)),
+            String::from("alu.vhd, mul.vhd")
+        );
+    }
+
+    #[test]
+    fn ignore_target_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let target_dir = root.join("target");
+        std::fs::write(
+            root.join("Orbit.toml"),
+            "[ip]\nname = \"alu\"\nversion = \"1.0.0\"\nuuid = \"a1b2c3d4e5f6g7h8i9j0k1l2m\"\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("alu.vhd"), "entity alu is end;").unwrap();
+        let before = Snapshot::capture(&root, &target_dir);
+        assert_eq!(before.len(), 2);
+
+        // outputs written by a run are not changes to the ip
+        let out_dir = target_dir.join("sim");
+        std::fs::create_dir_all(out_dir.join("logs")).unwrap();
+        std::fs::write(out_dir.join("blueprint.tsv"), "VHDL\twork\talu.vhd").unwrap();
+        std::fs::write(out_dir.join(".env"), "ORBIT_TOP=alu").unwrap();
+        std::fs::write(out_dir.join("logs").join("sim.log"), "done").unwrap();
+        assert_eq!(
+            before.diff(&Snapshot::capture(&root, &target_dir)),
+            Vec::<String>::new()
+        );
+
+        std::fs::write(root.join("alu.vhd"), "entity alu is end entity;").unwrap();
+        assert_eq!(
+            before.diff(&Snapshot::capture(&root, &target_dir)),
+            vec![filesystem::into_std_str(root.join("alu.vhd"))]
+        );
+    }
+}
@@ -23,7 +23,7 @@ use crate::core::{
     ip::IpSpec,
     lang::{lexer::Position, LangIdentifier},
     pkgid::PkgPart,
-    version::{AnyVersion, PartialVersion, Version},
+    version::{AnyVersion, Version, VersionReq},
     visibility::Visibility,
 };
 
@@ -57,10 +57,44 @@ pub enum Error {
     ChildProcErrorCode(i32),
     #[error("terminated by signal")]
     ChildProcTerminated,
+    #[error("timed out after {0}s")]
+    ChildProcTimedOut(u64),
+    #[error("interrupted")]
+    ChildProcInterrupted,
     #[error("no target named {0:?}{1}")]
     TargetNotFound(String, Hint),
     #[error("a target must be specified{0}")]
     TargetNotSpecified(Hint),
+    #[error("target {0:?} extends a target named {1:?} that does not exist{2}")]
+    TargetExtendsNotFound(String, String, Hint),
+    #[error("targets extend each other in a cycle: {0}")]
+    TargetExtendsCycle(String),
+    #[error("target {0:?} must define a command or steps, or extend a target that defines them")]
+    TargetMissingCommand(String),
+    #[error("target {0:?} cannot define both a command and steps")]
+    TargetCommandAndSteps(String),
+    #[error("target {0:?} defines more than one step named {1:?}")]
+    TargetStepDuplicate(String, String),
+    #[error("target {0:?} does not define any steps")]
+    TargetHasNoSteps(String),
+    #[error("target {0:?} has no step named {1:?}; available steps are: {2}")]
+    TargetStepNotFound(String, String, String),
+    #[error("starting step {0:?} comes after ending step {1:?}")]
+    TargetStepRangeInvalid(String, String),
+    #[error(
+        "target {0:?} runs steps and cannot accept a command or arguments from the command-line"
+    )]
+    TargetStepsNoOverride(String),
+    #[error("step {0:?} ({1} of {2}) failed: {3}")]
+    TargetStepFailed(String, usize, usize, LastError),
+    #[error("target {0:?} has an invalid {1} pattern: {2}")]
+    TargetPatternInvalid(String, String, LastError),
+    #[error("target {0:?} only supports {1}; the blueprint contains unsupported files:\n{2}")]
+    TargetLanguageNotSupported(String, String, String),
+    #[error("output matched the fail pattern: {0:?}")]
+    TargetOutputMatchedFail(String),
+    #[error("output did not match the pass pattern {0:?}")]
+    TargetOutputMissingPass(String),
     #[error("failed to execute target process: {0}")]
     TargetProcFailed(LastError),
     #[error("failed to execute protocol process: {0}")]
@@ -100,7 +134,7 @@ pub enum Error {
     #[error("failed to parse ip name: {0}")]
     IpNameParseFailed(LastError),
     #[error("listed version {0} does not match ip's actual version {1}")]
-    DependencyIpRelativeBadVersion(PartialVersion, Version),
+    DependencyIpRelativeBadVersion(VersionReq, Version),
     #[error("listed name {0} does not match ip's actual name {1}")]
     DependencyIpRelativeBadName(PkgPart, PkgPart),
     #[error("failed to load lockfile: {0}")]
@@ -113,6 +147,22 @@ pub enum Error {
     TestbenchRequired,
     #[error("top \"{0}\" is not tested in testbench \"{1}\"{2}")]
     TopNotInTestbench(LangIdentifier, LangIdentifier, Hint),
+    #[error("testbench patterns can only be used with \"--all-benches\"")]
+    TestbenchPatternsWithoutAll,
+    #[error("option \"--tb\" cannot be used with \"--all-benches\"")]
+    TestbenchWithAllBenches,
+    #[error("option \"--jobs\" can only be used with \"--all-benches\"")]
+    JobsWithoutAllBenches,
+    #[error("option \"--jobs\" must be at least 1")]
+    JobsZero,
+    #[error("cannot set generics without a {0}{1}")]
+    GenericsWithoutUnit(String, Hint),
+    #[error("unit {0:?} has no generic named {1:?}{2}")]
+    GenericNotFound(String, String, Hint),
+    #[error("no testbenches found matching: {0}")]
+    TestbenchesNotFound(String),
+    #[error("{0} of {1} testbenches failed")]
+    TestbenchesFailed(usize, usize),
     #[error("lockfile entry \"{0}\" is not queued for installation (missing download)")]
     EntryMissingDownload(IpSpec),
     #[error("lockfile entry \"{0}\" is not queued for installation")]
@@ -123,6 +173,8 @@ pub enum Error {
     DownloadFoundManyIps(usize, String, Hint),
     #[error("lockfile is missing or out of date{0}")]
     PublishMissingLockfile(Hint),
+    #[error("attributes entry {0} in the ip manifest has an invalid files pattern: {1}")]
+    AttributesPatternInvalid(usize, LastError),
     #[error("the ip manifest's source field is required to publish, but is undefined")]
     PublishMissingSource,
     #[error("ip {0} is already published to at least one of the specified channels")]
@@ -149,6 +201,10 @@ pub enum Error {
     PublishFailedCheckpoint(LastError),
     #[error("cyclic dependency with local ip \"{0}\"")]
     CyclicDependencyIp(PkgPart),
+    #[error("ip \"{0}\" is not a dependency of the local ip")]
+    DependencyNotFound(PkgPart),
+    #[error("no version of ip \"{0}\" in the catalog satisfies \"{1}\"{2}")]
+    DependencyVersionNotFound(PkgPart, VersionReq, Hint),
     #[error("failed to get uuid for ip \"{0}\" due to missing or corrupted lockfile{1}")]
     RequiredUuuidMissing(IpSpec, Hint),
     #[error("failed to find a version matching \"{0}\"{1}")]
@@ -238,6 +294,8 @@ pub enum Hint {
     ShowConfigFiles,
     ConfirmUuidChange(String),
     SolveNamespaceCollision,
+    DidYouMean(String),
+    ShowUnitGenerics(String),
 }
 
 impl Display for Hint {
@@ -247,6 +305,11 @@ impl Display for Hint {
                 "use `orbit info {0} --units` to display available units",
                 spec
             )),
+            Self::DidYouMean(s) => Some(format!("did you mean {:?}?", s)),
+            Self::ShowUnitGenerics(unit) => Some(format!(
+                "use `orbit get {0} --component` to see the unit's generics",
+                unit
+            )),
             _ => None,
         };
         let message = match self {
@@ -259,7 +322,9 @@ impl Display for Hint {
             Self::ResolveDuplicateIds1 => HINT_1,
             Self::ResolveDuplicateIds2 => HINT_2,
             Self::ShowAvailableUnitsLocal => "use `orbit info --units` to display available units",
-            Self::ShowAvailableUnitsExternal(_) => mixed_prompt.as_ref().unwrap(),
+            Self::ShowAvailableUnitsExternal(_)
+            | Self::DidYouMean(_)
+            | Self::ShowUnitGenerics(_) => mixed_prompt.as_ref().unwrap(),
             Self::DutSpecify => "use the \"--dut\" option to specify the design under test",
             Self::WantsTestbench => {
                 "use `orbit test` and its \"--tb\" option to select testbenches"
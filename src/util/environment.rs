@@ -231,6 +231,25 @@ pub fn save_environment(env: &Environment, root: &std::path::PathBuf) -> Result<
     Ok(())
 }
 
+/// Collects the variables from the current environment that are still
+/// inherited by a process with an isolated environment.
+///
+/// A variable is kept if it is on the built-in allowlist, is listed in `keep`,
+/// or is set by orbit itself.
+pub fn read_allowed_vars(keep: &[String]) -> Vec<(String, String)> {
+    let is_match = |a: &str, b: &str| match cfg!(windows) {
+        true => a.eq_ignore_ascii_case(b),
+        false => a == b,
+    };
+    std::env::vars()
+        .filter(|(key, _)| {
+            key.starts_with(ORBIT_PREFIX) == true
+                || ISOLATED_ENV_ALLOWLIST.iter().any(|k| is_match(k, key)) == true
+                || keep.iter().any(|k| is_match(k, key)) == true
+        })
+        .collect()
+}
+
 pub struct Environment(BTreeSet<EnvVar>);
 
 impl Environment {
@@ -296,14 +315,19 @@ pub const ORBIT_TARGET: &str = "ORBIT_TARGET";
 pub const ORBIT_TOP_NAME: &str = "ORBIT_TOP_NAME";
 pub const ORBIT_TOP_FILE: &str = "ORBIT_TOP_FILE";
 pub const ORBIT_TOP_JSON: &str = "ORBIT_TOP_JSON";
+pub const ORBIT_TOPS_JSON: &str = "ORBIT_TOPS_JSON";
 
 pub const ORBIT_TB_NAME: &str = "ORBIT_TB_NAME";
 pub const ORBIT_TB_FILE: &str = "ORBIT_TB_FILE";
 pub const ORBIT_TB_JSON: &str = "ORBIT_TB_JSON";
+pub const ORBIT_TBS_JSON: &str = "ORBIT_TBS_JSON";
 
 pub const ORBIT_DUT_NAME: &str = "ORBIT_DUT_NAME";
 pub const ORBIT_DUT_FILE: &str = "ORBIT_DUT_FILE";
 pub const ORBIT_DUT_JSON: &str = "ORBIT_DUT_JSON";
+pub const ORBIT_DUTS_JSON: &str = "ORBIT_DUTS_JSON";
+
+pub const ORBIT_GENERICS: &str = "ORBIT_GENERICS";
 
 pub const ORBIT_BLUEPRINT: &str = "ORBIT_BLUEPRINT";
 pub const ORBIT_TARGET_DIR: &str = "ORBIT_TARGET_DIR";
@@ -312,3 +336,31 @@ pub const ORBIT_OUT_DIR: &str = "ORBIT_OUT_DIR";
 pub const ORBIT_CHAN_INDEX: &str = "ORBIT_CHAN_INDEX";
 
 pub const ORBIT_ENV_PREFIX: &str = "ORBIT_ENV_";
+
+pub const ORBIT_PREFIX: &str = "ORBIT_";
+
+/// The variables that processes commonly require from the operating system, which
+/// are kept when a target isolates its environment.
+pub const ISOLATED_ENV_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LOGNAME",
+    "SHELL",
+    "TERM",
+    "LANG",
+    "TMPDIR",
+    "NO_COLOR",
+    "TEMP",
+    "TMP",
+    "USERNAME",
+    "USERPROFILE",
+    "SYSTEMROOT",
+    "SYSTEMDRIVE",
+    "WINDIR",
+    "COMSPEC",
+    "PATHEXT",
+];
+
+/// Separates multiple values stored within a single environment variable.
+pub const LIST_SEPARATOR: &str = ";";
@@ -31,6 +31,7 @@ use std::path::PathBuf;
 use std::path::{Component, Path};
 
 use super::anyerror::Fault;
+use super::subprocess;
 
 /// Recursively walks the given `path` and ignores files defined in a .gitignore file.
 ///
@@ -398,19 +399,37 @@ impl Standardize for PathBuf {
 ///
 /// Performs a fix to allow .bat files to be searched on windows given the option
 /// is enabled through environment variables.
+///
+/// When `isolate` is `true`, the process does not inherit the current
+/// environment and only receives the variables in `envs`. When `own_group` is
+/// `true`, the process starts in a new process group. When `capture` is `true`,
+/// the process's output is piped back to orbit instead of the terminal.
 pub fn invoke(
     cwd: &PathBuf,
     cmd: &String,
     args: &Vec<String>,
     try_again: bool,
     envs: HashMap<&String, &String>,
+    isolate: bool,
+    own_group: bool,
+    capture: bool,
 ) -> std::io::Result<std::process::Child> {
-    match std::process::Command::new(cmd)
+    let mut proc = std::process::Command::new(cmd);
+    // only pass along the given environment variables
+    if isolate == true {
+        proc.env_clear();
+    }
+    subprocess::set_process_group(&mut proc, own_group);
+    let output = || match capture {
+        true => std::process::Stdio::piped(),
+        false => std::process::Stdio::inherit(),
+    };
+    match proc
         .current_dir(cwd)
         .args(args)
         .envs(&envs)
-        .stdout(std::process::Stdio::inherit())
-        .stderr(std::process::Stdio::inherit())
+        .stdout(output())
+        .stderr(output())
         .spawn()
     {
         Ok(r) => Ok(r),
@@ -422,7 +441,16 @@ pub fn invoke(
                     None => true,
                 };
             if repeat == true && e.kind() == std::io::ErrorKind::NotFound {
-                invoke(cwd, &format!("{}.bat", cmd), args, false, envs)
+                invoke(
+                    cwd,
+                    &format!("{}.bat", cmd),
+                    args,
+                    false,
+                    envs,
+                    isolate,
+                    own_group,
+                    capture,
+                )
             } else {
                 Err(e)
             }
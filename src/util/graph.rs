@@ -17,7 +17,8 @@
 
 /// Basic graph data structure
 /// - source: http://smallcultfollowing.com/babysteps/blog/2015/04/06/modeling-graphs-in-rust-using-vector-indices/
-use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
 
 use super::graphmap::GraphMap;
 
@@ -290,6 +291,52 @@ impl<V, E> Graph<V, E> {
         order
     }
 
+    /// Performs topological sort where ties between nodes that are ready to be
+    /// processed are broken by choosing the node with the smallest `key`.
+    ///
+    /// Unlike [Graph::topological_sort], the order does not depend on the order
+    /// in which nodes were added to the graph when every node's key is unique.
+    pub fn topological_sort_by_key<K: Ord, F: Fn(NodeIndex) -> K>(&self, key: F) -> Vec<NodeIndex> {
+        let mut order = Vec::<NodeIndex>::with_capacity(self.node_count());
+        let keys: Vec<K> = (0..self.node_count()).map(|i| key(i)).collect();
+        // count the remaining dependencies for each node
+        let mut remaining: Vec<usize> = (0..self.node_count())
+            .map(|i| self.predecessors(i).collect::<HashSet<NodeIndex>>().len())
+            .collect();
+        let mut ready = BinaryHeap::new();
+        for (i, count) in remaining.iter().enumerate() {
+            if *count == 0 {
+                ready.push(Reverse((&keys[i], i)));
+            }
+        }
+        // process the smallest node able to be completed
+        while let Some(Reverse((_, current))) = ready.pop() {
+            order.push(current);
+            for i in self.successors(current).collect::<HashSet<NodeIndex>>() {
+                remaining[i] -= 1;
+                if remaining[i] == 0 {
+                    ready.push(Reverse((&keys[i], i)));
+                }
+            }
+        }
+        order
+    }
+
+    /// Performs [Graph::topological_sort_by_key] on the entire graph and then
+    /// only selects the minimal number of affected nodes needed to process up
+    /// to `target`.
+    pub fn minimal_topological_sort_by_key<K: Ord, F: Fn(NodeIndex) -> K>(
+        &self,
+        target: NodeIndex,
+        key: F,
+    ) -> Vec<NodeIndex> {
+        let effected_nodes: HashSet<usize> = self.depth_first_search(target).into_iter().collect();
+        self.topological_sort_by_key(key)
+            .into_iter()
+            .filter(|f| effected_nodes.contains(f) == true)
+            .collect()
+    }
+
     /// Performs topological sort on the entire graph and then only selects the
     /// minimal number of affected nodes needed process up to `target`.
     pub fn minimal_topological_sort(&self, target: NodeIndex) -> Vec<NodeIndex> {
@@ -710,6 +757,24 @@ mod test {
         assert_eq!(g.topological_sort(), vec![2, 3, 1, 5, 6, 4, 0]);
     }
 
+    #[test]
+    fn topological_sort_by_key() {
+        let g = basic_graph();
+        assert_eq!(g.topological_sort_by_key(|i| i), vec![0, 1, 3, 2]);
+        // ties are broken by the smallest key
+        assert_eq!(g.topological_sort_by_key(|i| Reverse(i)), vec![0, 3, 1, 2]);
+
+        let g = binary_tree();
+        assert_eq!(
+            g.topological_sort_by_key(|i| Reverse(i)),
+            vec![6, 5, 4, 3, 2, 1, 0]
+        );
+        assert_eq!(
+            g.minimal_topological_sort_by_key(4, |i| Reverse(i)),
+            vec![6, 5, 4]
+        );
+    }
+
     #[test]
     fn add_node_and_has_node() {
         let mut g: Graph<(), ()> = Graph::new();
@@ -0,0 +1,286 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! A test report in the JUnit XML format.
+//!
+//! The report contains a single test suite for the working ip, with one test
+//! case for every testbench that was ran by a target.
+
+use crate::util::anyerror::Fault;
+use crate::util::logfile::Timestamp;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// The file within the target's output directory that stores the report.
+pub const REPORT_FILE: &str = "junit.xml";
+
+/// The number of lines from the end of a testbench's output to include in its
+/// test case.
+pub const EXCERPT_LINES: usize = 50;
+
+/// The result of running a testbench.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Verdict {
+    Pass,
+    /// The target ran but determined the testbench failed.
+    Fail(String),
+    /// The target could not be ran for the testbench.
+    Error(String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TestCase {
+    name: String,
+    classname: String,
+    time: Duration,
+    verdict: Verdict,
+    stdout: Option<String>,
+}
+
+impl TestCase {
+    /// Creates a test case for the testbench `name` that tests the `dut`.
+    ///
+    /// Only the last lines of the `stdout` are kept in the test case.
+    pub fn new(
+        name: &str,
+        dut: &str,
+        time: Duration,
+        verdict: Verdict,
+        stdout: Option<&str>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            classname: dut.to_string(),
+            time: time,
+            verdict: verdict,
+            stdout: stdout.map(|s| excerpt(s, EXCERPT_LINES)),
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_time(&self) -> &Duration {
+        &self.time
+    }
+
+    pub fn get_verdict(&self) -> &Verdict {
+        &self.verdict
+    }
+
+    pub fn is_pass(&self) -> bool {
+        self.verdict == Verdict::Pass
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TestSuite {
+    name: String,
+    target: String,
+    timestamp: String,
+    cases: Vec<TestCase>,
+}
+
+impl TestSuite {
+    /// Creates a test suite named `name` for the results of running the
+    /// `target`, starting now.
+    pub fn new(name: &str, target: &str, cases: Vec<TestCase>) -> Self {
+        Self {
+            name: name.to_string(),
+            target: target.to_string(),
+            timestamp: Timestamp::now().to_iso(),
+            cases: cases,
+        }
+    }
+
+    /// Formats the test suite as a JUnit XML document.
+    pub fn to_xml(&self) -> String {
+        let failures = self
+            .cases
+            .iter()
+            .filter(|c| matches!(c.verdict, Verdict::Fail(_)))
+            .count();
+        let errors = self
+            .cases
+            .iter()
+            .filter(|c| matches!(c.verdict, Verdict::Error(_)))
+            .count();
+        let time: f64 = self.cases.iter().map(|c| c.time.as_secs_f64()).sum();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml += &format!(
+            "<testsuites tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">\n",
+            self.cases.len(),
+            failures,
+            errors,
+            time
+        );
+        xml += &format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"0\" time=\"{:.3}\" timestamp=\"{}\">\n",
+            escape(&self.name),
+            self.cases.len(),
+            failures,
+            errors,
+            time,
+            self.timestamp
+        );
+        xml += "    <properties>\n";
+        xml += &format!(
+            "      <property name=\"target\" value=\"{}\"/>\n",
+            escape(&self.target)
+        );
+        xml += "    </properties>\n";
+        for case in &self.cases {
+            xml += &format!(
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\">\n",
+                escape(&case.name),
+                escape(&case.classname),
+                case.time.as_secs_f64()
+            );
+            match &case.verdict {
+                Verdict::Pass => (),
+                Verdict::Fail(msg) => {
+                    xml += &format!(
+                        "      <failure message=\"{}\">{}</failure>\n",
+                        escape(msg),
+                        escape(msg)
+                    )
+                }
+                Verdict::Error(msg) => {
+                    xml += &format!(
+                        "      <error message=\"{}\">{}</error>\n",
+                        escape(msg),
+                        escape(msg)
+                    )
+                }
+            }
+            if let Some(stdout) = &case.stdout {
+                xml += &format!("      <system-out>{}</system-out>\n", escape(stdout));
+            }
+            xml += "    </testcase>\n";
+        }
+        xml += "  </testsuite>\n";
+        xml += "</testsuites>\n";
+        xml
+    }
+
+    /// Writes the report to the `output_path` and returns the path to the file.
+    pub fn save(&self, output_path: &PathBuf) -> Result<PathBuf, Fault> {
+        std::fs::create_dir_all(output_path)?;
+        let path = output_path.join(REPORT_FILE);
+        std::fs::write(&path, self.to_xml())?;
+        Ok(path)
+    }
+}
+
+/// Keeps only the last `n` lines of the `text`.
+fn excerpt(text: &str, n: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines.len().saturating_sub(n);
+    lines[start..].join("\n")
+}
+
+/// Replaces the characters that cannot appear as-is within xml text or
+/// attribute values.
+///
+/// Control characters other than whitespace are not allowed in xml at all, so
+/// they are removed.
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            '\t' | '\n' | '\r' => result.push(c),
+            c if (c as u32) < 0x20 => (),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn escape_xml() {
+        assert_eq!(
+            escape("a < b && \"c\"\x1b[31m"),
+            "a &lt; b &amp;&amp; &quot;c&quot;[31m"
+        );
+    }
+
+    #[test]
+    fn suite_to_xml() {
+        let output = (0..60)
+            .map(|i| format!("line {}", i))
+            .collect::<Vec<String>>()
+            .join("\n");
+        let mut suite = TestSuite::new(
+            "alu",
+            "ghdl",
+            vec![
+                TestCase::new(
+                    "alu_tb",
+                    "alu",
+                    Duration::from_millis(1500),
+                    Verdict::Pass,
+                    Some("ok"),
+                ),
+                TestCase::new(
+                    "mul_tb",
+                    "mul",
+                    Duration::from_millis(250),
+                    Verdict::Fail(String::from("assertion <failed>")),
+                    Some(&output),
+                ),
+                TestCase::new(
+                    "div_tb",
+                    "",
+                    Duration::ZERO,
+                    Verdict::Error(String::from("missing file")),
+                    None,
+                ),
+            ],
+        );
+        suite.timestamp = String::from("2026-10-17T12:00:00Z");
+        let xml = suite.to_xml();
+        assert_eq!(
+            xml.contains("<testsuites tests=\"3\" failures=\"1\" errors=\"1\" time=\"1.750\">"),
+            true
+        );
+        assert_eq!(xml.contains("<testsuite name=\"alu\" tests=\"3\" failures=\"1\" errors=\"1\" skipped=\"0\" time=\"1.750\" timestamp=\"2026-10-17T12:00:00Z\">"), true);
+        assert_eq!(
+            xml.contains("<testcase name=\"alu_tb\" classname=\"alu\" time=\"1.500\">\n      <system-out>ok</system-out>\n    </testcase>"),
+            true
+        );
+        assert_eq!(
+            xml.contains("<failure message=\"assertion &lt;failed&gt;\">"),
+            true
+        );
+        assert_eq!(xml.contains("<error message=\"missing file\">"), true);
+        // only the end of the output is kept
+        assert_eq!(xml.contains("line 9\n"), false);
+        assert_eq!(xml.contains("<system-out>line 10\n"), true);
+        assert_eq!(xml.contains("line 59</system-out>"), true);
+    }
+}
@@ -0,0 +1,518 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! A log file that records the output of child processes.
+//!
+//! The output is copied to the log file as it is read from the child, while
+//! still being streamed to the terminal unless the log is quiet. Only the last
+//! lines of output are kept in memory, which is all that is needed when the
+//! output is checked without saving a file.
+
+use crate::core::target::OutputCheck;
+use crate::error::Error;
+use crate::util::anyerror::Fault;
+use crate::util::environment::ORBIT_PREFIX;
+use crate::util::junit::EXCERPT_LINES;
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::process::Child;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The directory within the target's output directory that stores the logs.
+pub const LOGS_DIR: &str = "logs";
+
+/// The number of lines from the end of the log to display when a quiet
+/// process fails.
+pub const TAIL_LINES: usize = 20;
+
+/// The longest line of output that is inspected before it is split.
+const MAX_LINE_LEN: usize = 8192;
+
+#[derive(Debug, Clone)]
+pub struct LogFile {
+    path: Option<PathBuf>,
+    record: Arc<Mutex<Record>>,
+    quiet: bool,
+}
+
+/// What is written to a log while its processes run.
+///
+/// The notes orbit writes are tracked by their location in the file, so they
+/// are never confused with the output of the processes.
+#[derive(Debug)]
+struct Record {
+    file: Option<File>,
+    /// The size of the file before the log began writing to it.
+    start: u64,
+    /// The size of the file after the last write.
+    len: u64,
+    /// The ranges of bytes in the file that hold notes from orbit.
+    notes: Vec<Range<u64>>,
+    /// The last lines of the processes' output.
+    recent: VecDeque<String>,
+    check: Option<OutputCheck>,
+    /// The state of the check before the current process's first attempt.
+    retry: Option<OutputCheck>,
+}
+
+impl Record {
+    fn new(file: Option<File>, start: u64) -> Self {
+        Self {
+            file: file,
+            start: start,
+            len: start,
+            notes: Vec::new(),
+            recent: VecDeque::with_capacity(EXCERPT_LINES),
+            check: None,
+            retry: None,
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        if let Some(file) = &mut self.file {
+            file.write_all(bytes)?;
+            self.len += bytes.len() as u64;
+        }
+        Ok(())
+    }
+
+    /// Accepts a complete `line` of output from a process.
+    fn push_line(&mut self, line: &[u8]) {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim_end_matches(['\n', '\r']);
+        if let Some(check) = &mut self.check {
+            check.inspect(line);
+        }
+        if self.recent.len() == EXCERPT_LINES {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(line.to_string());
+    }
+}
+
+impl LogFile {
+    /// Creates a new log file for the run named `name` within the logs
+    /// directory of the `output_path`.
+    ///
+    /// When `quiet` is `true`, the output is only written to the log file.
+    pub fn create(output_path: &PathBuf, name: &str, quiet: bool) -> Result<Self, Fault> {
+        let dir = output_path.join(LOGS_DIR);
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{}-{}.log", name, Timestamp::now().to_compact()));
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        let start = file.metadata()?.len();
+        Ok(Self {
+            path: Some(path),
+            record: Arc::new(Mutex::new(Record::new(Some(file), start))),
+            quiet: quiet,
+        })
+    }
+
+    /// Creates a log that only keeps the last lines of output in memory while
+    /// the output is displayed.
+    pub fn memory() -> Self {
+        Self {
+            path: None,
+            record: Arc::new(Mutex::new(Record::new(None, 0))),
+            quiet: false,
+        }
+    }
+
+    /// Inspects each line of output with the `check` as it arrives.
+    pub fn checking(self, check: Option<OutputCheck>) -> Self {
+        self.record.lock().unwrap().check = check;
+        self
+    }
+
+    /// Begins recording the `attempt` of a process, counting from 1.
+    ///
+    /// A retry is checked as if the failed attempts before it never ran.
+    pub fn begin_attempt(&self, attempt: u32) {
+        let mut record = self.record.lock().unwrap();
+        match attempt {
+            1 => record.retry = record.check.clone(),
+            _ => record.check = record.retry.clone(),
+        }
+    }
+
+    /// Returns the path to the log's file, if it is saved to one.
+    pub fn get_path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    pub fn is_quiet(&self) -> bool {
+        self.quiet
+    }
+
+    /// Writes the header describing the process that is about to run.
+    ///
+    /// Only the variables from `envs` that belong to orbit are recorded.
+    pub fn write_header(
+        &self,
+        command: &str,
+        args: &[String],
+        cwd: &PathBuf,
+        envs: &HashMap<&String, &String>,
+    ) -> std::io::Result<()> {
+        let mut header = String::new();
+        header += &format!("# started: {}\n", Timestamp::now().to_iso());
+        header += &format!("# command: {}\n", command);
+        header += &format!(
+            "# args: {}\n",
+            args.iter()
+                .map(|a| format!("{:?}", a))
+                .collect::<Vec<String>>()
+                .join(" ")
+        );
+        header += &format!("# cwd: {}\n", cwd.display());
+        let mut vars: Vec<(&&String, &&String)> = envs
+            .iter()
+            .filter(|(k, _)| k.starts_with(ORBIT_PREFIX) == true)
+            .collect();
+        vars.sort();
+        for (k, v) in vars {
+            header += &format!("# {}={}\n", k, v);
+        }
+        self.write_note(&header)
+    }
+
+    /// Writes a line of text from orbit into the log.
+    pub fn write_note(&self, text: &str) -> std::io::Result<()> {
+        let mut record = self.record.lock().unwrap();
+        let start = record.len;
+        record.write(text.as_bytes())?;
+        if text.ends_with('\n') == false {
+            record.write(b"\n")?;
+        }
+        let end = record.len;
+        record.notes.push(start..end);
+        Ok(())
+    }
+
+    /// Copies the `child`'s output into the log as it arrives.
+    ///
+    /// The `child` must have been spawned with its standard output and error
+    /// piped. The returned handles finish once the child closes its output.
+    pub fn tee(&self, child: &mut Child) -> Vec<JoinHandle<()>> {
+        let mut handles = Vec::new();
+        if let Some(stdout) = child.stdout.take() {
+            handles.push(self.copy(stdout, std::io::stdout()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            handles.push(self.copy(stderr, std::io::stderr()));
+        }
+        handles
+    }
+
+    fn copy<R, W>(&self, mut src: R, mut term: W) -> JoinHandle<()>
+    where
+        R: Read + Send + 'static,
+        W: Write + Send + 'static,
+    {
+        let record = self.record.clone();
+        let quiet = self.quiet;
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 8192];
+            // the part of the current line that has been read so far
+            let mut line: Vec<u8> = Vec::new();
+            loop {
+                let n = match src.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => n,
+                };
+                if quiet == false {
+                    let _ = term.write_all(&buf[..n]);
+                    let _ = term.flush();
+                }
+                let mut record = record.lock().unwrap();
+                let _ = record.write(&buf[..n]);
+                for &b in &buf[..n] {
+                    line.push(b);
+                    if b == b'\n' || line.len() >= MAX_LINE_LEN {
+                        record.push_line(&line);
+                        line.clear();
+                    }
+                }
+            }
+            if line.is_empty() == false {
+                record.lock().unwrap().push_line(&line);
+            }
+        })
+    }
+
+    /// Determines if the output recorded so far passes the log's check.
+    pub fn verdict(&self) -> Result<(), Error> {
+        match &self.record.lock().unwrap().check {
+            Some(check) => check.verdict(),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the last lines of the processes' output.
+    pub fn excerpt(&self) -> String {
+        let record = self.record.lock().unwrap();
+        record
+            .recent
+            .iter()
+            .map(|l| l.as_str())
+            .collect::<Vec<&str>>()
+            .join("\n")
+    }
+
+    /// Displays where the log is saved after the process finishes.
+    ///
+    /// When the process `failed` and its output was kept out of the terminal,
+    /// the last lines of the log are displayed as well.
+    pub fn report(&self, failed: bool) -> Result<(), Fault> {
+        let path = match &self.path {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+        match failed == true && self.quiet == true {
+            true => {
+                crate::info!("last {} lines of log {:?}:", TAIL_LINES, path);
+                println!("{}", self.tail(TAIL_LINES)?);
+            }
+            false => crate::info!("log saved at: {:?}", path),
+        }
+        Ok(())
+    }
+
+    /// Reads the bytes this log wrote into its file.
+    fn read_bytes(&self) -> Result<Vec<u8>, Fault> {
+        let start = self.record.lock().unwrap().start as usize;
+        let bytes = match &self.path {
+            Some(p) => std::fs::read(p)?,
+            None => Vec::new(),
+        };
+        Ok(bytes.get(start..).unwrap_or_default().to_vec())
+    }
+
+    /// Reads only the output of the processes, leaving out the notes orbit
+    /// wrote into the log.
+    ///
+    /// A log without a file only has the last lines of output.
+    pub fn read_output(&self) -> Result<String, Fault> {
+        if self.path.is_none() {
+            return Ok(self.excerpt());
+        }
+        let (start, notes) = {
+            let record = self.record.lock().unwrap();
+            (record.start, record.notes.clone())
+        };
+        let bytes = self.read_bytes()?;
+        let mut output = Vec::with_capacity(bytes.len());
+        let mut pos = 0;
+        for note in notes {
+            let (lo, hi) = ((note.start - start) as usize, (note.end - start) as usize);
+            output.extend_from_slice(&bytes[pos..lo]);
+            pos = hi;
+        }
+        output.extend_from_slice(&bytes[pos..]);
+        Ok(String::from_utf8_lossy(&output).to_string())
+    }
+
+    /// Reads the last `n` lines of the log.
+    pub fn tail(&self, n: usize) -> Result<String, Fault> {
+        let contents = String::from_utf8_lossy(&self.read_bytes()?).to_string();
+        let lines: Vec<&str> = contents.lines().collect();
+        let start = lines.len().saturating_sub(n);
+        Ok(lines[start..].join("\n"))
+    }
+}
+
+/// A point in time in UTC.
+pub struct Timestamp {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+}
+
+impl Timestamp {
+    pub fn now() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self::from_unix(secs)
+    }
+
+    /// Converts the number of seconds since the unix epoch into a calendar date
+    /// and time.
+    fn from_unix(secs: u64) -> Self {
+        let days = (secs / 86400) as i64;
+        let rem = secs % 86400;
+        // shift the epoch to 0000-03-01 to place leap days at the end of a year
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+        Self {
+            year: year,
+            month: month,
+            day: day,
+            hour: (rem / 3600) as u32,
+            minute: (rem % 3600 / 60) as u32,
+            second: (rem % 60) as u32,
+        }
+    }
+
+    /// Formats the timestamp for use in a file name.
+    fn to_compact(&self) -> String {
+        format!(
+            "{:04}{:02}{:02}-{:02}{:02}{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+
+    /// Formats the timestamp according to ISO 8601.
+    pub fn to_iso(&self) -> String {
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::target::Target;
+    use std::str::FromStr;
+
+    #[test]
+    fn format_timestamp() {
+        assert_eq!(Timestamp::from_unix(0).to_iso(), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            Timestamp::from_unix(951825600).to_iso(),
+            "2000-02-29T12:00:00Z"
+        );
+        assert_eq!(
+            Timestamp::from_unix(1792243845).to_compact(),
+            "20261017-133045"
+        );
+    }
+
+    #[test]
+    fn header_and_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().to_path_buf();
+        let log = LogFile::create(&out, "sim", true).unwrap();
+        assert_eq!(
+            log.get_path().unwrap().parent().unwrap(),
+            out.join(LOGS_DIR)
+        );
+
+        let (target, name, home) = (
+            String::from("ORBIT_TARGET"),
+            String::from("sim"),
+            String::from("HOME"),
+        );
+        let envs = HashMap::from([(&target, &name), (&home, &name)]);
+        log.write_header("vsim", &[String::from("-c")], &out, &envs)
+            .unwrap();
+        for i in 0..30 {
+            log.write_note(&format!("line {}", i)).unwrap();
+        }
+        let contents = std::fs::read_to_string(log.get_path().unwrap()).unwrap();
+        assert_eq!(contents.contains("# command: vsim\n# args: \"-c\"\n"), true);
+        assert_eq!(contents.contains("# ORBIT_TARGET=sim\n"), true);
+        assert_eq!(contents.contains("HOME"), false);
+        assert_eq!(log.tail(2).unwrap(), String::from("line 28\nline 29"));
+        assert_eq!(log.read_output().unwrap(), String::new());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn output_apart_from_notes() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = LogFile::create(&dir.path().to_path_buf(), "sim", true).unwrap();
+        log.write_note("# step: sim (1 of 1)").unwrap();
+        // simulators may begin their own lines like the notes
+        let mut child = std::process::Command::new("sh")
+            .args(["-c", "echo '# result: 3 errors'; echo '# ** Note: done'"])
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        for h in log.tee(&mut child) {
+            h.join().unwrap();
+        }
+        child.wait().unwrap();
+        log.write_note("# result: success").unwrap();
+        assert_eq!(
+            log.read_output().unwrap(),
+            String::from("# result: 3 errors\n# ** Note: done\n")
+        );
+        assert_eq!(
+            log.excerpt(),
+            String::from("# result: 3 errors\n# ** Note: done")
+        );
+    }
+
+    #[test]
+    fn memory_output() {
+        let target = Target::from_str(
+            r#"
+name = "sim"
+command = "vsim"
+fail-pattern = "^# \\*\\* Error"
+"#,
+        )
+        .unwrap();
+        let log = LogFile::memory().checking(target.output_check().unwrap());
+        assert_eq!(log.get_path(), None);
+        log.write_note("# step: compile (1 of 2)").unwrap();
+        for i in 0..EXCERPT_LINES + 10 {
+            log.record
+                .lock()
+                .unwrap()
+                .push_line(format!("line {}\n", i).as_bytes());
+        }
+        assert_eq!(log.verdict().is_ok(), true);
+        log.record
+            .lock()
+            .unwrap()
+            .push_line(b"# ** Error: x != 1\r\n");
+        log.write_note("# result: success").unwrap();
+
+        let output = log.read_output().unwrap();
+        assert_eq!(output.lines().count(), EXCERPT_LINES);
+        assert_eq!(output.starts_with("line 11\n"), true);
+        assert_eq!(output.ends_with("\n# ** Error: x != 1"), true);
+        assert_eq!(
+            log.verdict().unwrap_err().to_string(),
+            Error::TargetOutputMatchedFail(String::from("# ** Error: x != 1")).to_string()
+        );
+    }
+}
@@ -22,8 +22,11 @@ pub mod environment;
 pub mod filesystem;
 pub mod graph;
 pub mod graphmap;
+pub mod junit;
+pub mod logfile;
 pub mod overdetsys;
 pub mod prompt;
 pub mod seqalin;
 pub mod sha256;
 pub mod strcmp;
+pub mod subprocess;
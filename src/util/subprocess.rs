@@ -0,0 +1,232 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Supervises a spawned child process until it exits, times out, or orbit is
+//! interrupted.
+//!
+//! A child that has a time limit is placed in its own process group so the
+//! entire group, including any processes it spawned, can be stopped together.
+//! Such a child is not in the terminal's foreground process group, so it is
+//! stopped if it tries to read from the terminal.
+
+use std::process::{Child, Command, ExitStatus};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+/// How frequently to check on a child process that has a time limit.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Set once orbit receives an interrupt while waiting on a child process.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// The number of child processes currently being waited on.
+static WAITING: AtomicUsize = AtomicUsize::new(0);
+
+/// Ensures the interrupt handler is only installed once.
+static HANDLER: Once = Once::new();
+
+/// The way a supervised child process finished.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Exited(ExitStatus),
+    TimedOut,
+    Interrupted,
+}
+
+/// Configures the `cmd` to start in a new process group when `own_group` is
+/// `true`.
+///
+/// A process in its own group no longer receives the terminal's interrupts
+/// directly, so they are forwarded by [wait].
+pub fn set_process_group(cmd: &mut Command, own_group: bool) {
+    #[cfg(unix)]
+    if own_group == true {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    #[cfg(not(unix))]
+    let _ = (cmd, own_group);
+}
+
+/// Waits on the `child` to finish, stopping it once the `timeout` elapses.
+///
+/// The `child` must have been spawned in its own process group when a
+/// `timeout` is given. An interrupt received by orbit while waiting is
+/// forwarded to the child, which is then allowed to exit on its own.
+pub fn wait(child: &mut Child, timeout: Option<Duration>) -> std::io::Result<Outcome> {
+    handle_interrupts();
+    let _waiting = Waiting::new();
+    let timeout = match timeout {
+        Some(t) => t,
+        // the child shares orbit's process group and receives interrupts directly
+        None => {
+            let status = child.wait()?;
+            return Ok(match INTERRUPTED.load(Ordering::SeqCst) {
+                true => Outcome::Interrupted,
+                false => Outcome::Exited(status),
+            });
+        }
+    };
+    let deadline = Instant::now() + timeout;
+    let mut forwarded = false;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(match forwarded {
+                true => Outcome::Interrupted,
+                false => Outcome::Exited(status),
+            });
+        }
+        if forwarded == false && INTERRUPTED.load(Ordering::SeqCst) == true {
+            interrupt_group(child);
+            forwarded = true;
+        }
+        if Instant::now() >= deadline {
+            kill_group(child)?;
+            child.wait()?;
+            return Ok(Outcome::TimedOut);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Sends an interrupt to every process in the `child`'s process group.
+fn interrupt_group(child: &Child) {
+    #[cfg(unix)]
+    unsafe {
+        libc::killpg(child.id() as libc::pid_t, libc::SIGINT);
+    }
+    // the console already delivers the interrupt to the child
+    #[cfg(not(unix))]
+    let _ = child;
+}
+
+/// Forcibly stops every process in the `child`'s process group.
+fn kill_group(child: &mut Child) -> std::io::Result<()> {
+    #[cfg(unix)]
+    unsafe {
+        libc::killpg(child.id() as libc::pid_t, libc::SIGKILL);
+    }
+    #[cfg(windows)]
+    {
+        // stop the process along with its descendants
+        let _ = Command::new("taskkill")
+            .args(["/T", "/F", "/PID", &child.id().to_string()])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status();
+    }
+    // make sure the child itself is stopped in case the group could not be
+    match child.kill() {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::InvalidInput => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Installs orbit's interrupt handler for the remainder of the process.
+///
+/// An interrupt received while a child process is being waited on is recorded
+/// so it can be forwarded to the child and reported once the child exits. Any
+/// other interrupt terminates orbit as usual.
+pub fn handle_interrupts() {
+    HANDLER.call_once(|| {
+        #[cfg(unix)]
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = on_interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            action.sa_flags = libc::SA_RESTART;
+            libc::sigemptyset(&mut action.sa_mask);
+            libc::sigaction(libc::SIGINT, &action, std::ptr::null_mut());
+        }
+    });
+}
+
+/// Checks if orbit has been interrupted while waiting on a child process.
+///
+/// Once interrupted, orbit should not start any more processes.
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+#[cfg(unix)]
+extern "C" fn on_interrupt(_: libc::c_int) {
+    match WAITING.load(Ordering::SeqCst) {
+        // terminate as if no handler was installed
+        0 => unsafe {
+            libc::signal(libc::SIGINT, libc::SIG_DFL);
+            libc::raise(libc::SIGINT);
+        },
+        _ => INTERRUPTED.store(true, Ordering::SeqCst),
+    }
+}
+
+/// Marks a child process as being waited on for as long as it exists.
+///
+/// Several may exist at once when child processes are waited on from
+/// different threads.
+struct Waiting;
+
+impl Waiting {
+    fn new() -> Self {
+        WAITING.fetch_add(1, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for Waiting {
+    fn drop(&mut self) {
+        WAITING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stop_process_group_on_timeout() {
+        // the shell's background child must also be stopped
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("marker");
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", &format!("(sleep 1; touch {:?}) & sleep 5", marker)]);
+        set_process_group(&mut cmd, true);
+        let mut child = cmd.spawn().unwrap();
+
+        let start = Instant::now();
+        let outcome = wait(&mut child, Some(Duration::from_millis(200))).unwrap();
+        assert_eq!(outcome, Outcome::TimedOut);
+        assert_eq!(start.elapsed() < Duration::from_secs(2), true);
+
+        std::thread::sleep(Duration::from_millis(1200));
+        assert_eq!(marker.exists(), false);
+    }
+
+    #[test]
+    fn exit_before_timeout() {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "exit 3"]);
+        set_process_group(&mut cmd, true);
+        let mut child = cmd.spawn().unwrap();
+
+        match wait(&mut child, Some(Duration::from_secs(5))).unwrap() {
+            Outcome::Exited(status) => assert_eq!(status.code(), Some(3)),
+            _ => panic!("process did not exit on its own"),
+        }
+    }
+}